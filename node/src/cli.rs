@@ -7,6 +7,11 @@ pub struct Cli {
 
 	#[clap(flatten)]
 	pub run: RunCmd,
+
+	/// Author a block as soon as a transaction enters the pool instead of waiting for the next
+	/// BABE slot. GRANDPA finality is disabled in this mode. Intended for development and CI.
+	#[clap(long)]
+	pub instant_seal: bool,
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -0,0 +1,112 @@
+//! Benchmarking setup for pallet-did
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Did;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::{Currency, Get};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+use sp_std::{prelude::*, vec};
+
+fn funded<T: Config>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 2u32.into());
+}
+
+fn funded_did<T: Config>() -> T::AccountId {
+	let caller: T::AccountId = whitelisted_caller();
+	funded::<T>(&caller);
+	Did::<T>::create(RawOrigin::Signed(caller.clone()).into()).unwrap();
+	caller
+}
+
+/// Fill `did` with `MaxServices - 1` endpoints so the measured call hits the largest document.
+fn fill_services<T: Config>(did: &T::AccountId) {
+	let len = T::MaxLength::get() as usize;
+	for i in 1..T::MaxServices::get() {
+		let mut id = vec![0u8; len];
+		id[..4].copy_from_slice(&i.to_le_bytes());
+		Did::<T>::add_service(
+			RawOrigin::Signed(did.clone()).into(),
+			did.clone(),
+			id,
+			vec![0u8; len],
+		)
+		.unwrap();
+	}
+}
+
+benchmarks! {
+	create {
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(Dids::<T>::contains_key(&caller));
+	}
+
+	set_controller {
+		let did = funded_did::<T>();
+		let controller: T::AccountId = account("controller", 0, 0);
+	}: _(RawOrigin::Signed(did.clone()), did.clone(), controller.clone())
+	verify {
+		assert_eq!(Dids::<T>::get(&did).unwrap().controller, controller);
+	}
+
+	add_service {
+		let did = funded_did::<T>();
+		fill_services::<T>(&did);
+		let len = T::MaxLength::get() as usize;
+	}: _(RawOrigin::Signed(did.clone()), did.clone(), vec![1u8; len], vec![1u8; len])
+	verify {
+		assert_eq!(Dids::<T>::get(&did).unwrap().services.len() as u32, T::MaxServices::get());
+	}
+
+	remove_service {
+		let did = funded_did::<T>();
+		fill_services::<T>(&did);
+		let len = T::MaxLength::get() as usize;
+		Did::<T>::add_service(
+			RawOrigin::Signed(did.clone()).into(),
+			did.clone(),
+			vec![1u8; len],
+			vec![1u8; len],
+		)?;
+	}: _(RawOrigin::Signed(did.clone()), did.clone(), vec![1u8; len])
+	verify {
+		assert_eq!(Dids::<T>::get(&did).unwrap().services.len() as u32, T::MaxServices::get() - 1);
+	}
+
+	remove {
+		// Every service is added by a different controller, so each deposit is returned to a
+		// different account.
+		let s in 0 .. T::MaxServices::get();
+		let did = funded_did::<T>();
+		let len = T::MaxLength::get() as usize;
+		let mut controller = did.clone();
+		for i in 0 .. s {
+			let next: T::AccountId = account("controller", i, 0);
+			funded::<T>(&next);
+			Did::<T>::set_controller(
+				RawOrigin::Signed(controller).into(),
+				did.clone(),
+				next.clone(),
+			)?;
+			let mut id = vec![0u8; len];
+			id[..4].copy_from_slice(&i.to_le_bytes());
+			Did::<T>::add_service(
+				RawOrigin::Signed(next.clone()).into(),
+				did.clone(),
+				id,
+				vec![0u8; len],
+			)?;
+			controller = next;
+		}
+	}: _(RawOrigin::Signed(controller), did.clone())
+	verify {
+		assert!(!Dids::<T>::contains_key(&did));
+	}
+
+	impl_benchmark_test_suite!(Did, crate::mock::new_test_ext(), crate::mock::Test);
+}
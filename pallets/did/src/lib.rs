@@ -0,0 +1,271 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Registry of decentralized identifiers (DIDs).
+//!
+//! A DID is keyed by the account that created it. Each DID document records a controller, which
+//! may be rotated to a different key, and a bounded list of service endpoints. Other pallets can
+//! check DID ownership through [`DidResolver`].
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::*;
+
+/// Lookup used by other pallets to reference DIDs instead of bare accounts.
+pub trait DidResolver<AccountId> {
+	/// Whether `did` is registered.
+	fn exists(did: &AccountId) -> bool;
+	/// Whether `who` is the current controller of `did`.
+	fn is_controller(did: &AccountId, who: &AccountId) -> bool;
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::DidResolver;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, ReservableCurrency},
+		CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::Saturating;
+	use sp_std::prelude::*;
+
+	use crate::weights::WeightInfo;
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// Identifier of a service endpoint, unique within a DID document.
+	pub type ServiceIdOf<T> = BoundedVec<u8, <T as Config>::MaxLength>;
+
+	/// A service endpoint advertised by a DID, e.g. a messaging or credential-issuing URL.
+	#[derive(
+		CloneNoBound, Encode, Decode, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen,
+	)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct ServiceEndpoint<T: Config> {
+		pub id: ServiceIdOf<T>,
+		pub url: BoundedVec<u8, T::MaxLength>,
+		/// Account that added the endpoint and holds its deposit.
+		pub depositor: T::AccountId,
+		/// Amount reserved from `depositor` for this endpoint.
+		pub deposit: BalanceOf<T>,
+	}
+
+	/// The on-chain document of a DID.
+	#[derive(
+		CloneNoBound, Encode, Decode, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen,
+	)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct DidDocument<T: Config> {
+		/// Account allowed to manage this document.
+		pub controller: T::AccountId,
+		/// Amount reserved from the DID account when the document was created.
+		pub deposit: BalanceOf<T>,
+		pub services: BoundedVec<ServiceEndpoint<T>, T::MaxServices>,
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// Currency used for the registration deposit.
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// Amount reserved for as long as a DID is registered.
+		#[pallet::constant]
+		type DidDeposit: Get<BalanceOf<Self>>;
+		/// Amount reserved per byte of service id and url stored in a DID document.
+		#[pallet::constant]
+		type DepositPerByte: Get<BalanceOf<Self>>;
+		/// Maximum number of service endpoints per DID.
+		#[pallet::constant]
+		type MaxServices: Get<u32>;
+		/// Maximum length of a service id or url.
+		#[pallet::constant]
+		type MaxLength: Get<u32>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// DID documents, keyed by the account that created the DID.
+	#[pallet::storage]
+	#[pallet::getter(fn did)]
+	pub type Dids<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, DidDocument<T>>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A DID was registered. [did]
+		DidCreated(T::AccountId),
+		/// Control of a DID was handed to a new account. [did, controller]
+		ControllerChanged(T::AccountId, T::AccountId),
+		/// A service endpoint was added to a DID. [did, service_id]
+		ServiceAdded(T::AccountId, ServiceIdOf<T>),
+		/// A service endpoint was removed from a DID. [did, service_id]
+		ServiceRemoved(T::AccountId, ServiceIdOf<T>),
+		/// A DID was removed and its deposit returned. [did]
+		DidRemoved(T::AccountId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account already has a DID.
+		AlreadyExists,
+		/// No DID is registered for this account.
+		NotFound,
+		/// The signer is not the controller of the DID.
+		NotController,
+		/// A service id or url exceeds `MaxLength`.
+		TooLong,
+		/// The DID already has `MaxServices` service endpoints.
+		TooManyServices,
+		/// A service endpoint with this id already exists.
+		ServiceExists,
+		/// No service endpoint with this id exists.
+		ServiceNotFound,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register a DID for the signer, who becomes its initial controller.
+		#[pallet::weight(T::WeightInfo::create())]
+		pub fn create(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!Dids::<T>::contains_key(&who), Error::<T>::AlreadyExists);
+
+			let deposit = T::DidDeposit::get();
+			T::Currency::reserve(&who, deposit)?;
+			Dids::<T>::insert(
+				&who,
+				DidDocument { controller: who.clone(), deposit, services: Default::default() },
+			);
+
+			Self::deposit_event(Event::DidCreated(who));
+			Ok(())
+		}
+
+		/// Hand control of `did` to `controller`. Only callable by the current controller.
+		#[pallet::weight(T::WeightInfo::set_controller())]
+		pub fn set_controller(
+			origin: OriginFor<T>,
+			did: T::AccountId,
+			controller: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Dids::<T>::try_mutate(&did, |maybe_doc| -> DispatchResult {
+				let doc = maybe_doc.as_mut().ok_or(Error::<T>::NotFound)?;
+				ensure!(doc.controller == who, Error::<T>::NotController);
+				doc.controller = controller.clone();
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::ControllerChanged(did, controller));
+			Ok(())
+		}
+
+		/// Add a service endpoint to `did`, reserving `DepositPerByte` for each byte of `id` and
+		/// `url` from the signer. Only callable by the controller.
+		#[pallet::weight(T::WeightInfo::add_service())]
+		pub fn add_service(
+			origin: OriginFor<T>,
+			did: T::AccountId,
+			id: Vec<u8>,
+			url: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let id: ServiceIdOf<T> = id.try_into().map_err(|_| Error::<T>::TooLong)?;
+			let url: BoundedVec<u8, T::MaxLength> =
+				url.try_into().map_err(|_| Error::<T>::TooLong)?;
+
+			Dids::<T>::try_mutate(&did, |maybe_doc| -> DispatchResult {
+				let doc = maybe_doc.as_mut().ok_or(Error::<T>::NotFound)?;
+				ensure!(doc.controller == who, Error::<T>::NotController);
+				ensure!(!doc.services.iter().any(|s| s.id == id), Error::<T>::ServiceExists);
+				let len = (id.len() + url.len()) as u32;
+				let deposit = T::DepositPerByte::get().saturating_mul(len.into());
+				doc.services
+					.try_push(ServiceEndpoint {
+						id: id.clone(),
+						url,
+						depositor: who.clone(),
+						deposit,
+					})
+					.map_err(|_| Error::<T>::TooManyServices)?;
+				T::Currency::reserve(&who, deposit)?;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::ServiceAdded(did, id));
+			Ok(())
+		}
+
+		/// Remove the service endpoint `id` from `did` and return its deposit to the account that
+		/// added it. Only callable by the controller.
+		#[pallet::weight(T::WeightInfo::remove_service())]
+		pub fn remove_service(
+			origin: OriginFor<T>,
+			did: T::AccountId,
+			id: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let id: ServiceIdOf<T> = id.try_into().map_err(|_| Error::<T>::ServiceNotFound)?;
+
+			Dids::<T>::try_mutate(&did, |maybe_doc| -> DispatchResult {
+				let doc = maybe_doc.as_mut().ok_or(Error::<T>::NotFound)?;
+				ensure!(doc.controller == who, Error::<T>::NotController);
+				let index = doc
+					.services
+					.iter()
+					.position(|s| s.id == id)
+					.ok_or(Error::<T>::ServiceNotFound)?;
+				let service = doc.services.remove(index);
+				T::Currency::unreserve(&service.depositor, service.deposit);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::ServiceRemoved(did, id));
+			Ok(())
+		}
+
+		/// Remove `did`, returning its deposit to the DID account and each service deposit to the
+		/// account that added the service. Only callable by the controller.
+		#[pallet::weight(T::WeightInfo::remove(T::MaxServices::get()))]
+		pub fn remove(origin: OriginFor<T>, did: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let doc = Dids::<T>::get(&did).ok_or(Error::<T>::NotFound)?;
+			ensure!(doc.controller == who, Error::<T>::NotController);
+
+			T::Currency::unreserve(&did, doc.deposit);
+			for service in doc.services {
+				T::Currency::unreserve(&service.depositor, service.deposit);
+			}
+			Dids::<T>::remove(&did);
+
+			Self::deposit_event(Event::DidRemoved(did));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> DidResolver<T::AccountId> for Pallet<T> {
+		fn exists(did: &T::AccountId) -> bool {
+			Dids::<T>::contains_key(did)
+		}
+
+		fn is_controller(did: &T::AccountId, who: &T::AccountId) -> bool {
+			Dids::<T>::get(did).map_or(false, |doc| &doc.controller == who)
+		}
+	}
+}
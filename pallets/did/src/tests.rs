@@ -0,0 +1,104 @@
+use crate::{mock::*, DidResolver, Error};
+use frame_support::{assert_noop, assert_ok};
+
+#[test]
+fn create_reserves_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Did::create(Origin::signed(1)));
+		assert_eq!(Did::did(1).unwrap().controller, 1);
+		assert_eq!(Balances::reserved_balance(1), DID_DEPOSIT);
+		assert!(<Did as DidResolver<u64>>::exists(&1));
+
+		assert_noop!(Did::create(Origin::signed(1)), Error::<Test>::AlreadyExists);
+		// Account 3 cannot cover the deposit.
+		assert!(Did::create(Origin::signed(3)).is_err());
+	});
+}
+
+#[test]
+fn controller_can_be_rotated() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Did::create(Origin::signed(1)));
+		assert_noop!(Did::set_controller(Origin::signed(2), 1, 2), Error::<Test>::NotController);
+
+		assert_ok!(Did::set_controller(Origin::signed(1), 1, 2));
+		assert!(<Did as DidResolver<u64>>::is_controller(&1, &2));
+		assert!(!<Did as DidResolver<u64>>::is_controller(&1, &1));
+
+		// The original account no longer manages its DID.
+		assert_noop!(
+			Did::add_service(Origin::signed(1), 1, b"hub".to_vec(), b"https://a".to_vec()),
+			Error::<Test>::NotController
+		);
+		assert_ok!(Did::add_service(Origin::signed(2), 1, b"hub".to_vec(), b"https://a".to_vec()));
+	});
+}
+
+#[test]
+fn services_are_bounded_and_unique() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Did::create(Origin::signed(1)));
+		assert_ok!(Did::add_service(Origin::signed(1), 1, b"hub".to_vec(), b"https://a".to_vec()));
+		assert_noop!(
+			Did::add_service(Origin::signed(1), 1, b"hub".to_vec(), b"https://b".to_vec()),
+			Error::<Test>::ServiceExists
+		);
+		assert_noop!(
+			Did::add_service(Origin::signed(1), 1, b"msg".to_vec(), vec![0u8; 17]),
+			Error::<Test>::TooLong
+		);
+		assert_ok!(Did::add_service(Origin::signed(1), 1, b"msg".to_vec(), b"https://b".to_vec()));
+		assert_noop!(
+			Did::add_service(Origin::signed(1), 1, b"vc".to_vec(), b"https://c".to_vec()),
+			Error::<Test>::TooManyServices
+		);
+
+		assert_ok!(Did::remove_service(Origin::signed(1), 1, b"hub".to_vec()));
+		assert_eq!(Did::did(1).unwrap().services.len(), 1);
+		assert_noop!(
+			Did::remove_service(Origin::signed(1), 1, b"hub".to_vec()),
+			Error::<Test>::ServiceNotFound
+		);
+	});
+}
+
+#[test]
+fn services_reserve_per_byte_deposit_from_signer() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Did::create(Origin::signed(1)));
+		assert_ok!(Did::set_controller(Origin::signed(1), 1, 2));
+
+		// The deposit is taken from the controller adding the service, not from the DID account.
+		assert_ok!(Did::add_service(Origin::signed(2), 1, b"hub".to_vec(), b"https://a".to_vec()));
+		let service_deposit = 12 * DEPOSIT_PER_BYTE;
+		assert_eq!(Balances::reserved_balance(1), DID_DEPOSIT);
+		assert_eq!(Balances::reserved_balance(2), service_deposit);
+		let service = &Did::did(1).unwrap().services[0];
+		assert_eq!((service.depositor, service.deposit), (2, service_deposit));
+
+		// The deposit goes back to the account that paid it, even after another handover.
+		assert_ok!(Did::set_controller(Origin::signed(2), 1, 3));
+		assert_ok!(Did::remove_service(Origin::signed(3), 1, b"hub".to_vec()));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::reserved_balance(1), DID_DEPOSIT);
+	});
+}
+
+#[test]
+fn remove_returns_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Did::remove(Origin::signed(1), 1), Error::<Test>::NotFound);
+		assert_ok!(Did::create(Origin::signed(1)));
+		assert_ok!(Did::add_service(Origin::signed(1), 1, b"hub".to_vec(), b"https://a".to_vec()));
+		assert_ok!(Did::set_controller(Origin::signed(1), 1, 2));
+		assert_ok!(Did::add_service(Origin::signed(2), 1, b"msg".to_vec(), b"https://b".to_vec()));
+
+		assert_noop!(Did::remove(Origin::signed(1), 1), Error::<Test>::NotController);
+		assert_ok!(Did::remove(Origin::signed(2), 1));
+		assert!(Did::did(1).is_none());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 100);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(2), 100);
+	});
+}
@@ -0,0 +1,113 @@
+//! Weights for pallet_did.
+//!
+//! The values below are hand-written estimates, not benchmark output. The pallet has not been
+//! benchmarked yet; replace this file with generated weights by running:
+//!
+//! ```sh
+//! ./target/release/node-template benchmark pallet \
+//!     --chain dev \
+//!     --pallet pallet_did \
+//!     --extrinsic '*' \
+//!     --steps 50 \
+//!     --repeat 20 \
+//!     --output pallets/did/src/weights.rs
+//! ```
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_did.
+pub trait WeightInfo {
+	fn create() -> Weight;
+	fn set_controller() -> Weight;
+	fn add_service() -> Weight;
+	fn remove_service() -> Weight;
+	fn remove(s: u32, ) -> Weight;
+}
+
+/// Hand-written placeholder weights for pallet_did.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Did Dids (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn create() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Did Dids (r:1 w:1)
+	fn set_controller() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Did Dids (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn add_service() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Did Dids (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn remove_service() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Did Dids (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn remove(s: u32, ) -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add((5_000_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(s as Weight)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: Did Dids (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn create() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Did Dids (r:1 w:1)
+	fn set_controller() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Did Dids (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn add_service() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Did Dids (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn remove_service() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Did Dids (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn remove(s: u32, ) -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add((5_000_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(s as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(s as Weight)))
+	}
+}
@@ -0,0 +1,31 @@
+//! Benchmarking setup for pallet-faucet
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Faucet;
+use frame_benchmarking::{account, benchmarks};
+use frame_support::traits::{Currency, Get};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, Saturating};
+
+benchmarks! {
+	drip {
+		T::Currency::make_free_balance_be(
+			&Faucet::<T>::account_id(),
+			BalanceOf::<T>::max_value() / 2u32.into(),
+		);
+		let dest: T::AccountId = account("dest", 0, 0);
+		// A previous drip to `dest` that has expired, so the interval check reads storage.
+		LastDrip::<T>::insert(&dest, frame_system::Pallet::<T>::block_number());
+		frame_system::Pallet::<T>::set_block_number(
+			frame_system::Pallet::<T>::block_number().saturating_add(T::DripInterval::get()),
+		);
+		let nonce = (0..).find(|nonce| Faucet::<T>::is_valid_work(&dest, *nonce)).unwrap();
+	}: _(RawOrigin::None, dest.clone(), nonce)
+	verify {
+		assert_eq!(LastDrip::<T>::get(&dest), Some(frame_system::Pallet::<T>::block_number()));
+	}
+
+	impl_benchmark_test_suite!(Faucet, crate::mock::new_test_ext(), crate::mock::Test);
+}
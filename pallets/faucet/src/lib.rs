@@ -0,0 +1,268 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! A faucet for development and test networks.
+//!
+//! `drip` is an unsigned call, so freshly generated accounts without any balance can request
+//! funds. Since unsigned transactions cost nothing, every drip has to carry a proof of work, and
+//! drips are pooled at `UnsignedPriority`, which runtimes should keep at or below untipped signed
+//! transactions.
+//!
+//! Each destination can be funded at most once every `DripInterval` blocks, and at most
+//! `MaxDripsPerInterval` drips are made across all accounts in each interval. These checks and
+//! the proof of work are applied both when the transaction is validated and when it is
+//! dispatched. The `MaxDripsPerBlock` limit is only enforced on dispatch: the pool validates
+//! against the next block, for which no drips have been counted yet. Funds come from the pallet
+//! account, which has to be endowed at genesis or topped up by transfers.
+//!
+//! Unsigned drips carry no nonce. The block of the destination's last drip takes its place in
+//! the transaction's `provides` tag and in the proof of work, so a replayed drip is rejected as
+//! too soon and work done for one drip cannot be reused for the next.
+//!
+//! The interval budget is shared by all requesters, and unsigned drips cannot be attributed to
+//! anyone, so a single client can still claim the whole budget. To make that expensive, the
+//! required difficulty rises by one bit for every `PowDifficultyStep` drips already made in the
+//! current interval, doubling the work needed for each later drip.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, ExistenceRequirement},
+		PalletId,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::{
+		traits::{AccountIdConversion, Hash, Saturating, Zero},
+		SaturatedConversion,
+	};
+
+	use crate::weights::WeightInfo;
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// Custom validity error: the destination was funded less than `DripInterval` blocks ago.
+	pub const DRIP_TOO_SOON: u8 = 1;
+
+	/// Custom validity error: `MaxDripsPerInterval` drips have already been made this interval.
+	pub const DRIP_BUDGET_EXHAUSTED: u8 = 2;
+
+	/// Maximum number of blocks an unsigned drip stays valid in the pool.
+	pub const DRIP_LONGEVITY: u64 = 5;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// Currency dripped by the faucet.
+		type Currency: Currency<Self::AccountId>;
+		/// Identifier of the account holding the faucet's funds.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+		/// Amount transferred per drip.
+		#[pallet::constant]
+		type DripAmount: Get<BalanceOf<Self>>;
+		/// Minimum number of blocks between two drips to the same account.
+		#[pallet::constant]
+		type DripInterval: Get<Self::BlockNumber>;
+		/// Maximum number of drips per block.
+		#[pallet::constant]
+		type MaxDripsPerBlock: Get<u32>;
+		/// Maximum number of drips across all accounts per `DripInterval`.
+		#[pallet::constant]
+		type MaxDripsPerInterval: Get<u32>;
+		/// Number of leading zero bits required of a drip's proof of work hash at the start of
+		/// each interval.
+		#[pallet::constant]
+		type PowDifficulty: Get<u32>;
+		/// Number of drips in an interval after which the required difficulty rises by one bit.
+		/// Zero keeps the difficulty at `PowDifficulty`.
+		#[pallet::constant]
+		type PowDifficultyStep: Get<u32>;
+		/// Priority of unsigned drip transactions. Keep it at or below that of untipped signed
+		/// transactions so drips only use spare block space.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Block at which each account was last funded.
+	#[pallet::storage]
+	#[pallet::getter(fn last_drip)]
+	pub type LastDrip<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, T::BlockNumber>;
+
+	/// Number of drips made in the given block. Keeping the block number alongside the count
+	/// avoids having to reset it every block.
+	#[pallet::storage]
+	pub type BlockDrips<T: Config> = StorageValue<_, (T::BlockNumber, u32), ValueQuery>;
+
+	/// Number of drips made in the interval starting at the given block.
+	#[pallet::storage]
+	pub type IntervalDrips<T: Config> = StorageValue<_, (T::BlockNumber, u32), ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Funds were sent to an account. [dest, amount]
+		Dripped(T::AccountId, BalanceOf<T>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account was funded less than `DripInterval` blocks ago.
+		TooSoon,
+		/// `MaxDripsPerBlock` drips have already been made in this block.
+		BlockLimitReached,
+		/// `MaxDripsPerInterval` drips have already been made in this interval.
+		BudgetExhausted,
+		/// The proof of work does not meet the required difficulty.
+		InvalidWork,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Send `DripAmount` to `dest`. Submitted as an unsigned transaction, with a `nonce`
+		/// satisfying [`Pallet::is_valid_work`].
+		#[pallet::weight(T::WeightInfo::drip())]
+		pub fn drip(origin: OriginFor<T>, dest: T::AccountId, nonce: u64) -> DispatchResult {
+			ensure_none(origin)?;
+			Self::ensure_can_drip(&dest, nonce)?;
+
+			let amount = T::DripAmount::get();
+			T::Currency::transfer(
+				&Self::account_id(),
+				&dest,
+				amount,
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			let now = frame_system::Pallet::<T>::block_number();
+			LastDrip::<T>::insert(&dest, now);
+			BlockDrips::<T>::mutate(|(block, count)| {
+				if *block != now {
+					*block = now;
+					*count = 0;
+				}
+				*count += 1;
+			});
+			let start = Self::interval_start(now);
+			IntervalDrips::<T>::mutate(|(interval, count)| {
+				if *interval != start {
+					*interval = start;
+					*count = 0;
+				}
+				*count += 1;
+			});
+
+			Self::deposit_event(Event::Dripped(dest, amount));
+			Ok(())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let (dest, nonce) = match call {
+				Call::drip { dest, nonce } => (dest, *nonce),
+				_ => return InvalidTransaction::Call.into(),
+			};
+			Self::ensure_can_drip(dest, nonce).map_err(|e| match e {
+				Error::<T>::TooSoon => InvalidTransaction::Custom(DRIP_TOO_SOON),
+				Error::<T>::BudgetExhausted => InvalidTransaction::Custom(DRIP_BUDGET_EXHAUSTED),
+				Error::<T>::InvalidWork => InvalidTransaction::BadProof,
+				_ => InvalidTransaction::ExhaustsResources,
+			})?;
+
+			// Never outlive the interval, so a drip cannot sit in the pool until the destination
+			// becomes eligible again.
+			let longevity = T::DripInterval::get().saturated_into::<u64>().clamp(1, DRIP_LONGEVITY);
+			ValidTransaction::with_tag_prefix("Faucet")
+				.priority(T::UnsignedPriority::get())
+				.and_provides((dest, LastDrip::<T>::get(dest)))
+				.longevity(longevity)
+				.propagate(true)
+				.build()
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The account holding the faucet's funds.
+		pub fn account_id() -> T::AccountId {
+			T::PalletId::get().into_account_truncating()
+		}
+
+		/// Whether `nonce` is a valid proof of work for the next drip to `dest`: the hash of
+		/// `dest`, the block of its last drip and `nonce` must start with
+		/// [`Pallet::required_difficulty`] zero bits.
+		pub fn is_valid_work(dest: &T::AccountId, nonce: u64) -> bool {
+			let hash = T::Hashing::hash_of(&(dest, LastDrip::<T>::get(dest), nonce));
+			let mut zeros = 0;
+			for byte in hash.as_ref() {
+				zeros += byte.leading_zeros();
+				if *byte != 0 {
+					break
+				}
+			}
+			zeros >= Self::required_difficulty()
+		}
+
+		/// Number of leading zero bits the next drip's proof of work needs: `PowDifficulty`, plus
+		/// one for every `PowDifficultyStep` drips already made in the current interval.
+		pub fn required_difficulty() -> u32 {
+			let step = T::PowDifficultyStep::get();
+			if step == 0 {
+				return T::PowDifficulty::get()
+			}
+			let now = frame_system::Pallet::<T>::block_number();
+			let (interval, count) = IntervalDrips::<T>::get();
+			let spent = if interval == Self::interval_start(now) { count } else { 0 };
+			T::PowDifficulty::get().saturating_add(spent / step)
+		}
+
+		/// First block of the `DripInterval` containing `now`.
+		fn interval_start(now: T::BlockNumber) -> T::BlockNumber {
+			let interval = T::DripInterval::get();
+			if interval.is_zero() {
+				return now
+			}
+			now - now % interval
+		}
+
+		fn ensure_can_drip(dest: &T::AccountId, nonce: u64) -> Result<(), Error<T>> {
+			let now = frame_system::Pallet::<T>::block_number();
+			if let Some(last) = LastDrip::<T>::get(dest) {
+				ensure!(now >= last.saturating_add(T::DripInterval::get()), Error::<T>::TooSoon);
+			}
+			ensure!(Self::is_valid_work(dest, nonce), Error::<T>::InvalidWork);
+			let (interval, count) = IntervalDrips::<T>::get();
+			ensure!(
+				interval != Self::interval_start(now) || count < T::MaxDripsPerInterval::get(),
+				Error::<T>::BudgetExhausted
+			);
+			let (block, count) = BlockDrips::<T>::get();
+			ensure!(
+				block != now || count < T::MaxDripsPerBlock::get(),
+				Error::<T>::BlockLimitReached
+			);
+			Ok(())
+		}
+	}
+}
@@ -0,0 +1,211 @@
+use crate::{
+	mock::*, Call as FaucetCall, Error, DRIP_BUDGET_EXHAUSTED, DRIP_LONGEVITY, DRIP_TOO_SOON,
+};
+use frame_support::{assert_noop, assert_ok, traits::Currency};
+use sp_runtime::{
+	traits::ValidateUnsigned,
+	transaction_validity::{InvalidTransaction, TransactionSource, TransactionValidity},
+};
+
+fn validate(dest: u64) -> TransactionValidity {
+	validate_with(dest, 0)
+}
+
+fn validate_with(dest: u64, nonce: u64) -> TransactionValidity {
+	Faucet::validate_unsigned(TransactionSource::External, &FaucetCall::drip { dest, nonce })
+}
+
+#[test]
+fn drip_funds_new_account() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(validate(1));
+		assert_ok!(Faucet::drip(Origin::none(), 1, 0));
+		assert_eq!(Balances::free_balance(1), DRIP_AMOUNT);
+		assert_eq!(Balances::free_balance(Faucet::account_id()), 1_000 - DRIP_AMOUNT);
+		assert_eq!(Faucet::last_drip(1), Some(1));
+	});
+}
+
+#[test]
+fn drip_requires_unsigned_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Faucet::drip(Origin::signed(1), 1, 0), sp_runtime::DispatchError::BadOrigin);
+	});
+}
+
+#[test]
+fn drips_to_same_account_are_rate_limited() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Faucet::drip(Origin::none(), 1, 0));
+
+		System::set_block_number(10);
+		assert_eq!(validate(1), InvalidTransaction::Custom(DRIP_TOO_SOON).into());
+		assert_noop!(Faucet::drip(Origin::none(), 1, 0), Error::<Test>::TooSoon);
+
+		System::set_block_number(11);
+		assert_ok!(validate(1));
+		assert_ok!(Faucet::drip(Origin::none(), 1, 0));
+		assert_eq!(Balances::free_balance(1), 2 * DRIP_AMOUNT);
+	});
+}
+
+#[test]
+fn drips_per_block_are_capped_on_dispatch() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Faucet::drip(Origin::none(), 1, 0));
+		assert_ok!(Faucet::drip(Origin::none(), 2, 0));
+		assert_noop!(Faucet::drip(Origin::none(), 3, 0), Error::<Test>::BlockLimitReached);
+
+		// The pool validates against the next block, which has no drips counted yet.
+		System::set_block_number(2);
+		assert_ok!(validate(3));
+		assert_ok!(Faucet::drip(Origin::none(), 3, 0));
+	});
+}
+
+#[test]
+fn drips_per_interval_are_capped() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Faucet::drip(Origin::none(), 1, 0));
+		assert_ok!(Faucet::drip(Origin::none(), 2, 0));
+		System::set_block_number(2);
+		assert_ok!(Faucet::drip(Origin::none(), 3, 0));
+		assert_ok!(Faucet::drip(Origin::none(), 4, 0));
+
+		System::set_block_number(3);
+		assert_eq!(validate(5), InvalidTransaction::Custom(DRIP_BUDGET_EXHAUSTED).into());
+		assert_noop!(Faucet::drip(Origin::none(), 5, 0), Error::<Test>::BudgetExhausted);
+
+		// The budget is reset at the start of the next interval.
+		System::set_block_number(10);
+		assert_ok!(validate(5));
+		assert_ok!(Faucet::drip(Origin::none(), 5, 0));
+	});
+}
+
+#[test]
+fn drip_requires_proof_of_work() {
+	new_test_ext().execute_with(|| {
+		PowDifficulty::set(8);
+		let bad = (0..).find(|nonce| !Faucet::is_valid_work(&1, *nonce)).unwrap();
+		let good = (0..).find(|nonce| Faucet::is_valid_work(&1, *nonce)).unwrap();
+
+		assert_eq!(validate_with(1, bad), InvalidTransaction::BadProof.into());
+		assert_noop!(Faucet::drip(Origin::none(), 1, bad), Error::<Test>::InvalidWork);
+		assert_ok!(validate_with(1, good));
+		assert_ok!(Faucet::drip(Origin::none(), 1, good));
+	});
+}
+
+#[test]
+fn difficulty_rises_with_drips_this_interval() {
+	new_test_ext().execute_with(|| {
+		// A nonce for account 4 that meets 4 bits of difficulty but not 5.
+		let weak = (0..)
+			.find(|nonce| {
+				PowDifficulty::set(5);
+				let strong = Faucet::is_valid_work(&4, *nonce);
+				PowDifficulty::set(4);
+				!strong && Faucet::is_valid_work(&4, *nonce)
+			})
+			.unwrap();
+		PowDifficultyStep::set(2);
+		let drip = |dest: u64| {
+			let nonce = (0..).find(|nonce| Faucet::is_valid_work(&dest, *nonce)).unwrap();
+			assert_ok!(Faucet::drip(Origin::none(), dest, nonce));
+		};
+
+		assert_eq!(Faucet::required_difficulty(), 4);
+		assert_ok!(validate_with(4, weak));
+		drip(1);
+		assert_eq!(Faucet::required_difficulty(), 4);
+		drip(2);
+		assert_eq!(Faucet::required_difficulty(), 5);
+
+		System::set_block_number(2);
+		// Work that met the starting difficulty is no longer enough.
+		assert_eq!(validate_with(4, weak), InvalidTransaction::BadProof.into());
+		drip(3);
+		assert_eq!(Faucet::required_difficulty(), 5);
+
+		// The difficulty drops back at the start of the next interval.
+		System::set_block_number(10);
+		assert_eq!(Faucet::required_difficulty(), 4);
+		assert_ok!(validate_with(4, weak));
+	});
+}
+
+#[test]
+fn empty_faucet_fails() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::slash(&Faucet::account_id(), 1_000 - DRIP_AMOUNT / 2);
+		assert!(Faucet::drip(Origin::none(), 1, 0).is_err());
+		assert_eq!(Faucet::last_drip(1), None);
+	});
+}
+
+#[test]
+fn replayed_drip_is_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(validate(1));
+		assert_ok!(Faucet::drip(Origin::none(), 1, 0));
+
+		// The same transaction resubmitted in the same block or any later one within the interval.
+		for block in 1..=10 {
+			System::set_block_number(block);
+			assert_eq!(validate(1), InvalidTransaction::Custom(DRIP_TOO_SOON).into());
+		}
+		assert_noop!(Faucet::drip(Origin::none(), 1, 0), Error::<Test>::TooSoon);
+		assert_eq!(Balances::free_balance(1), DRIP_AMOUNT);
+	});
+}
+
+#[test]
+fn provides_tag_changes_after_each_drip() {
+	new_test_ext().execute_with(|| {
+		let first = validate(1).unwrap();
+		assert_eq!(first.provides.len(), 1);
+		assert!(first.requires.is_empty());
+		assert_eq!(first.longevity, DRIP_LONGEVITY);
+		assert_ne!(first.provides, validate(2).unwrap().provides);
+
+		assert_ok!(Faucet::drip(Origin::none(), 1, 0));
+		System::set_block_number(11);
+		// A fresh request for the same account does not collide with the one already included.
+		assert_ne!(validate(1).unwrap().provides, first.provides);
+	});
+}
+
+#[test]
+fn reordered_drips_are_all_applied() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(validate(1));
+		assert_ok!(validate(2));
+		// Included in the opposite order to validation.
+		assert_ok!(Faucet::drip(Origin::none(), 2, 0));
+		assert_ok!(Faucet::drip(Origin::none(), 1, 0));
+		assert_eq!(Balances::free_balance(1), DRIP_AMOUNT);
+		assert_eq!(Balances::free_balance(2), DRIP_AMOUNT);
+	});
+}
+
+#[test]
+fn drip_is_valid_on_fork_without_it() {
+	let mut fork_a = new_test_ext();
+	let mut fork_b = new_test_ext();
+	let tag = fork_a.execute_with(|| {
+		let tag = validate(1).unwrap().provides;
+		assert_ok!(Faucet::drip(Origin::none(), 1, 0));
+		tag
+	});
+	// The other fork never saw the drip, so it is still valid there, under the same tag so the
+	// pool treats both as one transaction.
+	fork_b.execute_with(|| {
+		assert_eq!(validate(1).unwrap().provides, tag);
+		assert_ok!(Faucet::drip(Origin::none(), 1, 0));
+	});
+	// Once included, the drip cannot be replayed on the fork it landed on.
+	fork_a.execute_with(|| {
+		assert_eq!(validate(1), InvalidTransaction::Custom(DRIP_TOO_SOON).into());
+	});
+}
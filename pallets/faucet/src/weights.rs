@@ -0,0 +1,53 @@
+//! Weights for pallet_faucet.
+//!
+//! The values below are hand-written estimates, not benchmark output. The pallet has not been
+//! benchmarked yet; replace this file with generated weights by running:
+//!
+//! ```sh
+//! ./target/release/node-template benchmark pallet \
+//!     --chain dev \
+//!     --pallet pallet_faucet \
+//!     --extrinsic '*' \
+//!     --steps 50 \
+//!     --repeat 20 \
+//!     --output pallets/faucet/src/weights.rs
+//! ```
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_faucet.
+pub trait WeightInfo {
+	fn drip() -> Weight;
+}
+
+/// Hand-written placeholder weights for pallet_faucet.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Faucet LastDrip (r:1 w:1)
+	// Storage: Faucet IntervalDrips (r:1 w:1)
+	// Storage: Faucet BlockDrips (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn drip() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: Faucet LastDrip (r:1 w:1)
+	// Storage: Faucet IntervalDrips (r:1 w:1)
+	// Storage: Faucet BlockDrips (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn drip() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+}
@@ -0,0 +1,21 @@
+//! Benchmarking setup for pallet-feature-flags
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as FeatureFlags;
+use frame_benchmarking::benchmarks;
+use frame_support::traits::{EnsureOrigin, Get};
+use sp_std::vec;
+
+benchmarks! {
+	set_feature {
+		let origin = T::ToggleOrigin::successful_origin();
+		let name = vec![0u8; T::MaxNameLength::get() as usize];
+	}: _<T::Origin>(origin, name.clone(), true)
+	verify {
+		assert!(<FeatureFlags<T> as FeatureGate>::is_enabled(&name));
+	}
+
+	impl_benchmark_test_suite!(FeatureFlags, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,129 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Governance-controlled feature flags.
+//!
+//! Features are identified by name, e.g. `b"auctions_enabled"`, and are disabled unless
+//! `ToggleOrigin` has enabled them. Other pallets query flags at dispatch time through
+//! [`FeatureGate`], which lets functionality be rolled out or switched off without a runtime
+//! upgrade.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::*;
+
+/// Read access to feature flags for other pallets.
+pub trait FeatureGate {
+	/// Whether the feature called `name` is enabled.
+	fn is_enabled(name: &[u8]) -> bool;
+}
+
+/// Every feature is enabled. Useful for pallets whose runtime does not use feature flags.
+impl FeatureGate for () {
+	fn is_enabled(_name: &[u8]) -> bool {
+		true
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::FeatureGate;
+	use frame_support::{pallet_prelude::*, traits::EnsureOrigin};
+	use frame_system::pallet_prelude::*;
+	use sp_std::prelude::*;
+
+	use crate::weights::WeightInfo;
+
+	/// Name of a feature.
+	pub type FeatureNameOf<T> = BoundedVec<u8, <T as Config>::MaxNameLength>;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// Origin allowed to enable and disable features.
+		type ToggleOrigin: EnsureOrigin<Self::Origin>;
+		/// Maximum length of a feature name.
+		#[pallet::constant]
+		type MaxNameLength: Get<u32>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Features that are currently enabled. Absent features are disabled.
+	#[pallet::storage]
+	pub type EnabledFeatures<T: Config> = StorageMap<_, Blake2_128Concat, FeatureNameOf<T>, ()>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig {
+		/// Features enabled at genesis.
+		pub enabled: Vec<Vec<u8>>,
+	}
+
+	#[cfg(feature = "std")]
+	impl Default for GenesisConfig {
+		fn default() -> Self {
+			Self { enabled: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			for name in &self.enabled {
+				let name: FeatureNameOf<T> =
+					name.clone().try_into().expect("feature name exceeds MaxNameLength");
+				EnabledFeatures::<T>::insert(name, ());
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A feature was enabled or disabled. [name, enabled]
+		FeatureToggled(FeatureNameOf<T>, bool),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The feature name exceeds `MaxNameLength`.
+		NameTooLong,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Enable or disable the feature called `name`.
+		#[pallet::weight(T::WeightInfo::set_feature())]
+		pub fn set_feature(origin: OriginFor<T>, name: Vec<u8>, enabled: bool) -> DispatchResult {
+			T::ToggleOrigin::ensure_origin(origin)?;
+			let name: FeatureNameOf<T> = name.try_into().map_err(|_| Error::<T>::NameTooLong)?;
+
+			if enabled {
+				EnabledFeatures::<T>::insert(&name, ());
+			} else {
+				EnabledFeatures::<T>::remove(&name);
+			}
+
+			Self::deposit_event(Event::FeatureToggled(name, enabled));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> FeatureGate for Pallet<T> {
+		fn is_enabled(name: &[u8]) -> bool {
+			FeatureNameOf::<T>::try_from(name.to_vec())
+				.map_or(false, |name| EnabledFeatures::<T>::contains_key(name))
+		}
+	}
+}
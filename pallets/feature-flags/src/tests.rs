@@ -0,0 +1,44 @@
+use crate::{mock::*, Error, FeatureGate};
+use frame_support::{assert_noop, assert_ok};
+
+fn enabled(name: &[u8]) -> bool {
+	<FeatureFlags as FeatureGate>::is_enabled(name)
+}
+
+#[test]
+fn genesis_features_are_enabled() {
+	new_test_ext().execute_with(|| {
+		assert!(enabled(b"breeding"));
+		assert!(!enabled(b"auctions"));
+	});
+}
+
+#[test]
+fn toggle_origin_sets_features() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			FeatureFlags::set_feature(Origin::signed(1), b"auctions".to_vec(), true),
+			sp_runtime::DispatchError::BadOrigin
+		);
+
+		assert_ok!(FeatureFlags::set_feature(Origin::root(), b"auctions".to_vec(), true));
+		assert!(enabled(b"auctions"));
+		System::assert_last_event(
+			crate::Event::FeatureToggled(b"auctions".to_vec().try_into().unwrap(), true).into(),
+		);
+
+		assert_ok!(FeatureFlags::set_feature(Origin::root(), b"breeding".to_vec(), false));
+		assert!(!enabled(b"breeding"));
+	});
+}
+
+#[test]
+fn long_names_are_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			FeatureFlags::set_feature(Origin::root(), vec![b'a'; 17], true),
+			Error::<Test>::NameTooLong
+		);
+		assert!(!enabled(&[b'a'; 17]));
+	});
+}
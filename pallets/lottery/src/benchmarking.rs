@@ -0,0 +1,47 @@
+//! Benchmarking setup for pallet-lottery
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Lottery;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::{Currency, Get};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+
+fn funded<T: Config>(who: &T::AccountId) {
+	T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 2u32.into());
+}
+
+benchmarks! {
+	buy_ticket {
+		// Fill all but one ticket so the bounded vector is at its largest and the per-account
+		// count scans every ticket.
+		for i in 1 .. T::MaxTickets::get() {
+			let buyer: T::AccountId = account("buyer", i, 0);
+			funded::<T>(&buyer);
+			Lottery::<T>::buy_ticket(RawOrigin::Signed(buyer).into())?;
+		}
+		let caller: T::AccountId = whitelisted_caller();
+		funded::<T>(&caller);
+	}: _(RawOrigin::Signed(caller))
+	verify {
+		assert_eq!(Tickets::<T>::get().len() as u32, T::MaxTickets::get());
+	}
+
+	draw {
+		for i in 0 .. T::MaxTickets::get() {
+			let buyer: T::AccountId = account("buyer", i, 0);
+			funded::<T>(&buyer);
+			Lottery::<T>::buy_ticket(RawOrigin::Signed(buyer).into())?;
+		}
+	}: {
+		Lottery::<T>::draw();
+	}
+	verify {
+		assert!(Tickets::<T>::get().is_empty());
+		assert_eq!(Round::<T>::get(), 1);
+	}
+
+	impl_benchmark_test_suite!(Lottery, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,202 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! A recurring lottery.
+//!
+//! Anyone can buy up to `MaxTicketsPerAccount` tickets for the current round; ticket payments
+//! accumulate in a pot held by the pallet account. Every `RoundLength` blocks a winning ticket is
+//! drawn using the configured randomness source and the whole pot is paid to its holder.
+//!
+//! The draw is only as unpredictable as `Randomness`. With `pallet_randomness_collective_flip`
+//! the block author can bias the outcome, so the pallet should only be used with a VRF-based
+//! source such as BABE's. Sales close `SalesCutoff` blocks before each draw, so that buyers
+//! cannot pick their tickets knowing the randomness the draw will use.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, ExistenceRequirement, Randomness},
+		PalletId,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{AccountIdConversion, Saturating, TrailingZeroInput, Zero};
+
+	use crate::weights::WeightInfo;
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// Currency used for tickets and payouts.
+		type Currency: Currency<Self::AccountId>;
+		/// Source of randomness for drawing the winner.
+		type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
+		/// Identifier of the account holding the pot.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+		/// Price of a single ticket.
+		#[pallet::constant]
+		type TicketPrice: Get<BalanceOf<Self>>;
+		/// Number of blocks between draws.
+		#[pallet::constant]
+		type RoundLength: Get<Self::BlockNumber>;
+		/// Maximum number of tickets sold per round.
+		#[pallet::constant]
+		type MaxTickets: Get<u32>;
+		/// Maximum number of tickets a single account may hold in a round.
+		#[pallet::constant]
+		type MaxTicketsPerAccount: Get<u32>;
+		/// Number of blocks before each draw during which no tickets are sold. Must cover the
+		/// period for which `Randomness` is known in advance, e.g. one epoch for
+		/// `pallet_babe::RandomnessFromOneEpochAgo`.
+		#[pallet::constant]
+		type SalesCutoff: Get<Self::BlockNumber>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Index of the current round.
+	#[pallet::storage]
+	#[pallet::getter(fn round)]
+	pub type Round<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// Holders of the tickets sold in the current round, one entry per ticket.
+	#[pallet::storage]
+	#[pallet::getter(fn tickets)]
+	pub type Tickets<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxTickets>, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A ticket was bought for the current round. [round, buyer]
+		TicketBought(u32, T::AccountId),
+		/// A round was drawn and its pot paid out. [round, winner, amount]
+		Winner(u32, T::AccountId, BalanceOf<T>),
+		/// A round ended without any tickets sold. [round]
+		NoTickets(u32),
+		/// The pot could not be paid to the drawn winner and carries over to the next round.
+		/// [round, winner, amount]
+		PayoutFailed(u32, T::AccountId, BalanceOf<T>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// All tickets for the current round have been sold.
+		SoldOut,
+		/// The account already holds `MaxTicketsPerAccount` tickets for the current round.
+		TooManyTickets,
+		/// Ticket sales for the current round have closed ahead of the draw.
+		SalesClosed,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let length = T::RoundLength::get();
+			if length.is_zero() || !(now % length).is_zero() {
+				return 0
+			}
+			Self::draw();
+			T::WeightInfo::draw()
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Buy a ticket for the current round, paying `TicketPrice` into the pot.
+		#[pallet::weight(T::WeightInfo::buy_ticket())]
+		pub fn buy_ticket(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::sales_open(), Error::<T>::SalesClosed);
+
+			Tickets::<T>::try_mutate(|tickets| -> DispatchResult {
+				let held = tickets.iter().filter(|holder| *holder == &who).count() as u32;
+				ensure!(held < T::MaxTicketsPerAccount::get(), Error::<T>::TooManyTickets);
+				tickets.try_push(who.clone()).map_err(|_| Error::<T>::SoldOut)?;
+				T::Currency::transfer(
+					&who,
+					&Self::account_id(),
+					T::TicketPrice::get(),
+					ExistenceRequirement::KeepAlive,
+				)
+			})?;
+
+			Self::deposit_event(Event::TicketBought(Self::round(), who));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The account holding the pot.
+		pub fn account_id() -> T::AccountId {
+			T::PalletId::get().into_account_truncating()
+		}
+
+		/// The amount the winner of the current round would receive.
+		pub fn pot() -> BalanceOf<T> {
+			T::Currency::free_balance(&Self::account_id())
+				.saturating_sub(T::Currency::minimum_balance())
+		}
+
+		/// Whether tickets can still be bought for the current round.
+		pub fn sales_open() -> bool {
+			let length = T::RoundLength::get();
+			if length.is_zero() {
+				return true
+			}
+			let now = frame_system::Pallet::<T>::block_number();
+			now % length < length.saturating_sub(T::SalesCutoff::get())
+		}
+
+		/// Pay the pot to a randomly chosen ticket holder and start the next round.
+		pub(crate) fn draw() {
+			let round = Round::<T>::mutate(|round| {
+				let current = *round;
+				*round = round.wrapping_add(1);
+				current
+			});
+			let tickets = Tickets::<T>::take();
+			if tickets.is_empty() {
+				Self::deposit_event(Event::NoTickets(round));
+				return
+			}
+
+			let (seed, _) = T::Randomness::random(&(T::PalletId::get(), round).encode());
+			let index = u32::decode(&mut TrailingZeroInput::new(seed.as_ref()))
+				.expect("input is padded with zeroes; qed") as usize %
+				tickets.len();
+			let winner = tickets[index].clone();
+
+			// The pot account keeps the existential deposit so it is never reaped.
+			let amount = Self::pot();
+			match T::Currency::transfer(
+				&Self::account_id(),
+				&winner,
+				amount,
+				ExistenceRequirement::KeepAlive,
+			) {
+				Ok(()) => Self::deposit_event(Event::Winner(round, winner, amount)),
+				Err(_) => Self::deposit_event(Event::PayoutFailed(round, winner, amount)),
+			}
+		}
+	}
+}
@@ -0,0 +1,126 @@
+use crate::{mock::*, Error, Event as LotteryEvent};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Hooks, LockableCurrency, WithdrawReasons},
+};
+
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		Lottery::on_initialize(System::block_number());
+	}
+}
+
+#[test]
+fn tickets_fill_the_pot() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Lottery::buy_ticket(Origin::signed(1)));
+		assert_ok!(Lottery::buy_ticket(Origin::signed(1)));
+		assert_ok!(Lottery::buy_ticket(Origin::signed(2)));
+		assert_eq!(Lottery::tickets().into_inner(), vec![1, 1, 2]);
+		assert_eq!(Balances::free_balance(1), 100 - 2 * TICKET_PRICE);
+		// The pot account keeps the existential deposit back from the payout.
+		assert_eq!(Lottery::pot(), 3 * TICKET_PRICE - 1);
+
+		assert_noop!(Lottery::buy_ticket(Origin::signed(3)), Error::<Test>::SoldOut);
+	});
+}
+
+#[test]
+fn tickets_per_account_are_capped() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Lottery::buy_ticket(Origin::signed(1)));
+		assert_ok!(Lottery::buy_ticket(Origin::signed(1)));
+		assert_noop!(Lottery::buy_ticket(Origin::signed(1)), Error::<Test>::TooManyTickets);
+		assert_ok!(Lottery::buy_ticket(Origin::signed(2)));
+	});
+}
+
+#[test]
+fn sales_close_before_the_draw() {
+	new_test_ext().execute_with(|| {
+		run_to_block(7);
+		assert_ok!(Lottery::buy_ticket(Origin::signed(1)));
+
+		run_to_block(8);
+		assert_noop!(Lottery::buy_ticket(Origin::signed(2)), Error::<Test>::SalesClosed);
+
+		// Sales reopen with the next round.
+		run_to_block(10);
+		assert_ok!(Lottery::buy_ticket(Origin::signed(2)));
+	});
+}
+
+#[test]
+fn ticket_requires_funds() {
+	new_test_ext().execute_with(|| {
+		assert!(Lottery::buy_ticket(Origin::signed(4)).is_err());
+		assert!(Lottery::tickets().is_empty());
+	});
+}
+
+#[test]
+fn draw_pays_a_ticket_holder() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Lottery::buy_ticket(Origin::signed(1)));
+		assert_ok!(Lottery::buy_ticket(Origin::signed(2)));
+		assert_ok!(Lottery::buy_ticket(Origin::signed(3)));
+		let pot = Lottery::pot();
+
+		run_to_block(9);
+		assert_eq!(Lottery::round(), 0);
+
+		run_to_block(10);
+		assert_eq!(Lottery::round(), 1);
+		assert!(Lottery::tickets().is_empty());
+		assert_eq!(Lottery::pot(), 0);
+
+		let winner = System::events()
+			.into_iter()
+			.find_map(|record| match record.event {
+				Event::Lottery(LotteryEvent::Winner(0, winner, amount)) => {
+					assert_eq!(amount, pot);
+					Some(winner)
+				},
+				_ => None,
+			})
+			.expect("a winner is drawn");
+		assert!([1, 2, 3].contains(&winner));
+		assert_eq!(Balances::free_balance(winner), 100 - TICKET_PRICE + pot);
+	});
+}
+
+#[test]
+fn failed_payout_carries_the_pot_over() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Lottery::buy_ticket(Origin::signed(1)));
+		assert_ok!(Lottery::buy_ticket(Origin::signed(2)));
+		let pot = Lottery::pot();
+
+		// A lock on the pot account makes the payout fail.
+		Balances::set_lock(*b"testlock", &Lottery::account_id(), u128::MAX, WithdrawReasons::all());
+		run_to_block(10);
+		assert_eq!(Lottery::round(), 1);
+		assert!(Lottery::tickets().is_empty());
+		assert_eq!(Lottery::pot(), pot);
+		assert!(System::events().into_iter().any(|record| matches!(
+			record.event,
+			Event::Lottery(LotteryEvent::PayoutFailed(0, _, amount)) if amount == pot
+		)));
+
+		// The next round pays out both pots.
+		Balances::remove_lock(*b"testlock", &Lottery::account_id());
+		assert_ok!(Lottery::buy_ticket(Origin::signed(3)));
+		run_to_block(20);
+		System::assert_last_event(LotteryEvent::Winner(1, 3, pot + TICKET_PRICE).into());
+	});
+}
+
+#[test]
+fn empty_round_is_skipped() {
+	new_test_ext().execute_with(|| {
+		run_to_block(10);
+		assert_eq!(Lottery::round(), 1);
+		System::assert_last_event(LotteryEvent::NoTickets(0).into());
+	});
+}
@@ -0,0 +1,70 @@
+//! Weights for pallet_lottery.
+//!
+//! The values below are hand-written estimates, not benchmark output. The pallet has not been
+//! benchmarked yet; replace this file with generated weights by running:
+//!
+//! ```sh
+//! ./target/release/node-template benchmark pallet \
+//!     --chain dev \
+//!     --pallet pallet_lottery \
+//!     --extrinsic '*' \
+//!     --steps 50 \
+//!     --repeat 20 \
+//!     --output pallets/lottery/src/weights.rs
+//! ```
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_lottery.
+pub trait WeightInfo {
+	fn buy_ticket() -> Weight;
+	fn draw() -> Weight;
+}
+
+/// Hand-written placeholder weights for pallet_lottery.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Lottery Tickets (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Lottery Round (r:1 w:0)
+	fn buy_ticket() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Lottery Round (r:1 w:1)
+	// Storage: Lottery Tickets (r:1 w:1)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn draw() -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: Lottery Tickets (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Lottery Round (r:1 w:0)
+	fn buy_ticket() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Lottery Round (r:1 w:1)
+	// Storage: Lottery Tickets (r:1 w:1)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	fn draw() -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+}
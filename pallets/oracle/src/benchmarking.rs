@@ -0,0 +1,237 @@
+//! Benchmarking setup for pallet-oracle
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as Oracle;
+use frame_benchmarking::{account, benchmarks};
+use frame_support::{
+	traits::{Currency, EnsureOrigin, Get, Hooks},
+	BoundedVec,
+};
+use frame_system::RawOrigin;
+use sp_runtime::{
+	traits::{Bounded, Zero},
+	Perbill,
+};
+use sp_std::{prelude::*, vec};
+
+fn feed_id<T: Config>(i: u32) -> FeedIdOf<T> {
+	let mut name = b"feed".to_vec();
+	name.extend_from_slice(&i.to_le_bytes());
+	name.try_into().unwrap()
+}
+
+/// A distinct URL of `MaxUrlLength` bytes.
+fn endpoint_url<T: Config>(i: u32) -> Vec<u8> {
+	let mut url = vec![b'a'; T::MaxUrlLength::get() as usize];
+	url[..4].copy_from_slice(&i.to_le_bytes());
+	url
+}
+
+/// Slashing rules under which every deviation from the median is slashed immediately.
+fn slashing_config<T: Config>() -> SlashingConfig<BalanceOf<T>> {
+	SlashingConfig {
+		bond: 100u32.into(),
+		tolerance: Perbill::zero(),
+		max_strikes: 1,
+		slash_fraction: Perbill::from_percent(50),
+	}
+}
+
+/// Register feed `i` with `feeders` funded and bonded feeders, returning its id and the feeder
+/// accounts.
+fn setup_feed<T: Config>(i: u32, feeders: u32) -> (FeedIdOf<T>, Vec<T::AccountId>) {
+	let feed = feed_id::<T>(i);
+	let origin = T::FeedOrigin::successful_origin();
+	Oracle::<T>::set_slashing_config(origin.clone(), Some(slashing_config::<T>())).unwrap();
+	Oracle::<T>::register_feed(origin.clone(), feed.to_vec(), 1u32.into(), 10u32.into(), 1)
+		.unwrap();
+	let accounts: Vec<T::AccountId> = (0..feeders).map(|f| account("feeder", f, 0)).collect();
+	for who in &accounts {
+		T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 2u32.into());
+		Oracle::<T>::add_feeder(origin.clone(), feed.clone(), who.clone()).unwrap();
+		Oracle::<T>::bond(RawOrigin::Signed(who.clone()).into(), feed.clone()).unwrap();
+	}
+	(feed, accounts)
+}
+
+benchmarks! {
+	register_feed {
+		let origin = T::FeedOrigin::successful_origin();
+		let name = vec![0u8; T::MaxNameLength::get() as usize];
+	}: _<T::Origin>(origin, name.clone(), 1u32.into(), 10u32.into(), 1)
+	verify {
+		let feed: FeedIdOf<T> = name.try_into().unwrap();
+		assert!(Feeds::<T>::contains_key(&feed));
+	}
+
+	remove_feed {
+		let (feed, feeders) = setup_feed::<T>(0, T::MaxFeeders::get());
+		Oracle::<T>::submit(RawOrigin::Signed(feeders[0].clone()).into(), feed.clone(), 1u32.into())?;
+		let origin = T::FeedOrigin::successful_origin();
+	}: _<T::Origin>(origin, feed.clone())
+	verify {
+		assert!(!Feeds::<T>::contains_key(&feed));
+	}
+
+	add_feeder {
+		let (feed, _) = setup_feed::<T>(0, T::MaxFeeders::get() - 1);
+		let origin = T::FeedOrigin::successful_origin();
+		let feeder: T::AccountId = account("new", 0, 0);
+	}: _<T::Origin>(origin, feed.clone(), feeder.clone())
+	verify {
+		assert!(Feeds::<T>::get(&feed).unwrap().feeders.contains(&feeder));
+	}
+
+	remove_feeder {
+		let (feed, feeders) = setup_feed::<T>(0, T::MaxFeeders::get());
+		let origin = T::FeedOrigin::successful_origin();
+		let feeder = feeders.last().unwrap().clone();
+	}: _<T::Origin>(origin, feed.clone(), feeder.clone())
+	verify {
+		assert!(!Feeds::<T>::get(&feed).unwrap().feeders.contains(&feeder));
+	}
+
+	submit {
+		let (feed, feeders) = setup_feed::<T>(0, T::MaxFeeders::get());
+		// Fill the round so the duplicate check scans every earlier submission.
+		for who in &feeders[1..] {
+			Oracle::<T>::submit(RawOrigin::Signed(who.clone()).into(), feed.clone(), 1u32.into())?;
+		}
+		let caller = feeders[0].clone();
+	}: _(RawOrigin::Signed(caller), feed.clone(), 1u32.into())
+	verify {
+		assert_eq!(Rounds::<T>::get(&feed).unwrap().submissions.len() as u32, T::MaxFeeders::get());
+	}
+
+	on_initialize {
+		let r in 0 .. T::MaxFeeds::get();
+		let mut feeds = Vec::new();
+		for i in 0 .. r {
+			let (feed, feeders) = setup_feed::<T>(i, T::MaxFeeders::get());
+			// A full history, so closing the round has to drop the oldest value.
+			let observation = TimestampedValue { value: i.into(), updated_at: Zero::zero() };
+			History::<T>::insert(
+				&feed,
+				BoundedVec::try_from(vec![observation; T::MaxHistory::get() as usize]).unwrap(),
+			);
+			// A previous value and a limit the round stays within, so the circuit breaker is
+			// checked but the value is still applied.
+			let previous =
+				TimestampedValue { value: T::MaxFeeders::get().into(), updated_at: Zero::zero() };
+			Values::<T>::insert(&feed, previous);
+			MaxChange::<T>::insert(&feed, Perbill::from_percent(100));
+			// Distinct values, so every feeder but the median one is slashed.
+			for (f, who) in feeders.iter().enumerate() {
+				let value = (f as u32).into();
+				Oracle::<T>::submit(RawOrigin::Signed(who.clone()).into(), feed.clone(), value)?;
+			}
+			feeds.push(feed);
+		}
+		let now = frame_system::Pallet::<T>::block_number() + 1u32.into();
+	}: {
+		Oracle::<T>::on_initialize(now);
+	}
+	verify {
+		for feed in &feeds {
+			assert!(Values::<T>::contains_key(feed));
+		}
+	}
+
+	set_slashing_config {
+		let origin = T::FeedOrigin::successful_origin();
+	}: _<T::Origin>(origin, Some(slashing_config::<T>()))
+	verify {
+		assert_eq!(Slashing::<T>::get(), Some(slashing_config::<T>()));
+	}
+
+	bond {
+		let (feed, _) = setup_feed::<T>(0, T::MaxFeeders::get() - 1);
+		let feeder: T::AccountId = account("new", 0, 0);
+		T::Currency::make_free_balance_be(&feeder, BalanceOf::<T>::max_value() / 2u32.into());
+		let origin = T::FeedOrigin::successful_origin();
+		Oracle::<T>::add_feeder(origin, feed.clone(), feeder.clone())?;
+	}: _(RawOrigin::Signed(feeder.clone()), feed.clone())
+	verify {
+		assert_eq!(Bonds::<T>::get(&feed, &feeder), Some(slashing_config::<T>().bond));
+	}
+
+	unbond {
+		let (feed, feeders) = setup_feed::<T>(0, T::MaxFeeders::get());
+		// Every other feeder has submitted, so the pending check scans a full round.
+		for who in &feeders[1..] {
+			Oracle::<T>::submit(RawOrigin::Signed(who.clone()).into(), feed.clone(), 1u32.into())?;
+		}
+		let caller = feeders[0].clone();
+	}: _(RawOrigin::Signed(caller.clone()), feed.clone())
+	verify {
+		assert!(!Bonds::<T>::contains_key(&feed, &caller));
+	}
+
+	reinstate {
+		let (feed, feeders) = setup_feed::<T>(0, T::MaxFeeders::get());
+		let feeder = feeders[0].clone();
+		Suspended::<T>::insert(&feed, &feeder, ());
+		Strikes::<T>::insert(&feed, &feeder, 1);
+		let origin = T::FeedOrigin::successful_origin();
+	}: _<T::Origin>(origin, feed.clone(), feeder.clone())
+	verify {
+		assert!(!Suspended::<T>::contains_key(&feed, &feeder));
+	}
+
+	set_max_change {
+		let (feed, _) = setup_feed::<T>(0, 0);
+		let origin = T::FeedOrigin::successful_origin();
+	}: _<T::Origin>(origin, feed.clone(), Some(Perbill::from_percent(10)))
+	verify {
+		assert_eq!(MaxChange::<T>::get(&feed), Some(Perbill::from_percent(10)));
+	}
+
+	pause_feed {
+		let (feed, feeders) = setup_feed::<T>(0, T::MaxFeeders::get());
+		Oracle::<T>::submit(RawOrigin::Signed(feeders[0].clone()).into(), feed.clone(), 1u32.into())?;
+		let origin = T::FeedOrigin::successful_origin();
+	}: _<T::Origin>(origin, feed.clone())
+	verify {
+		assert!(Paused::<T>::contains_key(&feed));
+		assert!(!Rounds::<T>::contains_key(&feed));
+	}
+
+	resume_feed {
+		let (feed, _) = setup_feed::<T>(0, 0);
+		Paused::<T>::insert(&feed, ());
+		let origin = T::FeedOrigin::successful_origin();
+	}: _<T::Origin>(origin, feed.clone())
+	verify {
+		assert!(!Paused::<T>::contains_key(&feed));
+	}
+
+	add_endpoint {
+		let (feed, _) = setup_feed::<T>(0, 0);
+		let origin = T::FeedOrigin::successful_origin();
+		// All but one slot taken, so the duplicate check scans a full list.
+		for i in 1 .. T::MaxEndpoints::get() {
+			Oracle::<T>::add_endpoint(origin.clone(), feed.clone(), endpoint_url::<T>(i))?;
+		}
+		let url = endpoint_url::<T>(0);
+	}: _<T::Origin>(origin, feed.clone(), url.clone())
+	verify {
+		assert!(Endpoints::<T>::get(&feed).iter().any(|e| e.to_vec() == url));
+	}
+
+	remove_endpoint {
+		let (feed, _) = setup_feed::<T>(0, 0);
+		let origin = T::FeedOrigin::successful_origin();
+		for i in 0 .. T::MaxEndpoints::get() {
+			Oracle::<T>::add_endpoint(origin.clone(), feed.clone(), endpoint_url::<T>(i))?;
+		}
+		// The last endpoint, so the lookup scans the whole list.
+		let url = endpoint_url::<T>(T::MaxEndpoints::get() - 1);
+	}: _<T::Origin>(origin, feed.clone(), url.clone())
+	verify {
+		assert!(!Endpoints::<T>::get(&feed).iter().any(|e| e.to_vec() == url));
+	}
+
+	impl_benchmark_test_suite!(Oracle, crate::mock::new_test_ext(), crate::mock::Test);
+}
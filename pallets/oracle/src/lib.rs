@@ -0,0 +1,879 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Registry of named oracle feeds.
+//!
+//! Governance registers feeds and the accounts allowed to feed them. The first submission to a
+//! feed opens a round which stays open for the feed's aggregation window; when the window closes
+//! the median of the submissions becomes the feed's value, provided enough feeders took part.
+//! Consumers read values through [`FeedProvider`], which hides values older than the feed's
+//! `max_age`. The last `MaxHistory` values of each feed are kept so consumers can use
+//! time-weighted averages through [`FeedHistory`] instead of spot values.
+//!
+//! Once `FeedOrigin` sets a [`SlashingConfig`], feeders have to bond before submitting. A feeder
+//! whose submissions deviate from the round's median by more than the tolerance for
+//! `max_strikes` consecutive rounds loses part of its bond to `Slash` and is suspended from the
+//! feed until `FeedOrigin` reinstates it.
+//!
+//! Feeds can be frozen so a compromised source cannot move the values other pallets rely on.
+//! `FeedOrigin` may pause a feed at any time, and may limit how far a single round can move a
+//! feed's value. A round exceeding that limit is not applied; the feed is paused instead, keeping
+//! its last value until `FeedOrigin` resumes it.
+//!
+//! `FeedOrigin` also keeps an allowlist of source URLs per feed. Feeders read it from chain state
+//! and rotate through it with [`Pallet::endpoint_for`], so sources can be changed for every
+//! feeder at once instead of each operator reconfiguring their node.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::*;
+
+/// Read access to aggregated feed values for other pallets.
+pub trait FeedProvider<FeedId, Value> {
+	/// The latest aggregated value of `feed`, or `None` if the feed is unknown or stale.
+	fn latest(feed: &FeedId) -> Option<Value>;
+}
+
+/// Time-weighted averages over the recent values of a feed.
+pub trait FeedHistory<FeedId, Value, BlockNumber> {
+	/// The time-weighted average of `feed` over the last `window` blocks, or `None` if the feed is
+	/// unknown or stale. Each value is weighted by the number of blocks it was current for.
+	fn twap(feed: &FeedId, window: BlockNumber) -> Option<Value>;
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::{FeedHistory, FeedProvider};
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, EnsureOrigin, Imbalance, OnUnbalanced, ReservableCurrency},
+		CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::{
+		traits::{AtLeast32BitUnsigned, Saturating, Zero},
+		PerThing, Perbill, SaturatedConversion,
+	};
+	use sp_std::prelude::*;
+
+	use crate::weights::WeightInfo;
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
+
+	/// Name of a feed, e.g. `b"DOT/USD"`.
+	pub type FeedIdOf<T> = BoundedVec<u8, <T as Config>::MaxNameLength>;
+
+	/// URL of a source feeders may query for a feed.
+	pub type EndpointOf<T> = BoundedVec<u8, <T as Config>::MaxUrlLength>;
+
+	/// Configuration of a registered feed.
+	#[derive(Encode, Decode, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	#[derive(CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound)]
+	pub struct FeedInfo<T: Config> {
+		/// Accounts allowed to submit to this feed.
+		pub feeders: BoundedVec<T::AccountId, T::MaxFeeders>,
+		/// Number of blocks a round stays open after its first submission.
+		pub window: T::BlockNumber,
+		/// Number of blocks after which an aggregated value is considered stale.
+		pub max_age: T::BlockNumber,
+		/// Submissions required for a round to produce a value.
+		pub min_submissions: u32,
+	}
+
+	/// Submissions collected for a feed while its aggregation window is open.
+	#[derive(Encode, Decode, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	#[derive(CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound)]
+	pub struct Round<T: Config> {
+		/// Block at which the round is aggregated.
+		pub closes_at: T::BlockNumber,
+		pub submissions: BoundedVec<(T::AccountId, T::Value), T::MaxFeeders>,
+	}
+
+	/// An aggregated value together with the block it was produced at.
+	#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct TimestampedValue<Value, BlockNumber> {
+		pub value: Value,
+		pub updated_at: BlockNumber,
+	}
+
+	/// Bonding and slashing rules for feeders, set by `FeedOrigin`.
+	#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct SlashingConfig<Balance> {
+		/// Amount a feeder has to bond per feed before submitting.
+		pub bond: Balance,
+		/// Allowed deviation from the median, relative to the median.
+		pub tolerance: Perbill,
+		/// Consecutive rounds outside the tolerance after which a feeder is slashed.
+		pub max_strikes: u32,
+		/// Portion of the bond slashed once `max_strikes` is reached.
+		pub slash_fraction: Perbill,
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// The type of values reported by feeders.
+		type Value: Parameter + Member + AtLeast32BitUnsigned + Copy + MaxEncodedLen;
+		/// Origin allowed to register feeds, manage their feeders and set the slashing rules.
+		type FeedOrigin: EnsureOrigin<Self::Origin>;
+		/// Currency in which feeders bond.
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// Handler for slashed bonds.
+		type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+		/// Maximum length of a feed name.
+		#[pallet::constant]
+		type MaxNameLength: Get<u32>;
+		/// Maximum number of registered feeds. Bounds the rounds closed in a single block.
+		#[pallet::constant]
+		type MaxFeeds: Get<u32>;
+		/// Maximum number of feeders per feed.
+		#[pallet::constant]
+		type MaxFeeders: Get<u32>;
+		/// Number of past values kept per feed for time-weighted averages.
+		#[pallet::constant]
+		type MaxHistory: Get<u32>;
+		/// Maximum number of allowed endpoints per feed.
+		#[pallet::constant]
+		type MaxEndpoints: Get<u32>;
+		/// Maximum length of an endpoint URL.
+		#[pallet::constant]
+		type MaxUrlLength: Get<u32>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Registered feeds.
+	#[pallet::storage]
+	#[pallet::getter(fn feed)]
+	pub type Feeds<T: Config> = StorageMap<_, Blake2_128Concat, FeedIdOf<T>, FeedInfo<T>>;
+
+	/// Number of registered feeds.
+	#[pallet::storage]
+	pub type FeedCount<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// Open rounds, keyed by feed.
+	#[pallet::storage]
+	#[pallet::getter(fn round)]
+	pub type Rounds<T: Config> = StorageMap<_, Blake2_128Concat, FeedIdOf<T>, Round<T>>;
+
+	/// Feeds whose open round closes at the given block, so `on_initialize` only reads the rounds
+	/// that are due.
+	#[pallet::storage]
+	pub type Closing<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<FeedIdOf<T>, T::MaxFeeds>,
+		ValueQuery,
+	>;
+
+	/// The latest aggregated value of each feed, regardless of staleness.
+	#[pallet::storage]
+	#[pallet::getter(fn value)]
+	pub type Values<T: Config> =
+		StorageMap<_, Blake2_128Concat, FeedIdOf<T>, TimestampedValue<T::Value, T::BlockNumber>>;
+
+	/// The last `MaxHistory` values of each feed, oldest first. Once full, the oldest value is
+	/// dropped for every new one.
+	#[pallet::storage]
+	#[pallet::getter(fn history)]
+	pub type History<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		FeedIdOf<T>,
+		BoundedVec<TimestampedValue<T::Value, T::BlockNumber>, T::MaxHistory>,
+		ValueQuery,
+	>;
+
+	/// Bonding and slashing rules. Feeders do not need to bond while this is unset.
+	#[pallet::storage]
+	#[pallet::getter(fn slashing_config)]
+	pub type Slashing<T: Config> = StorageValue<_, SlashingConfig<BalanceOf<T>>>;
+
+	/// Amount each feeder has bonded for a feed.
+	#[pallet::storage]
+	#[pallet::getter(fn bond_of)]
+	pub type Bonds<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		FeedIdOf<T>,
+		Blake2_128Concat,
+		T::AccountId,
+		BalanceOf<T>,
+	>;
+
+	/// Consecutive rounds in which a feeder's submission was outside the tolerance.
+	#[pallet::storage]
+	#[pallet::getter(fn strikes)]
+	pub type Strikes<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		FeedIdOf<T>,
+		Blake2_128Concat,
+		T::AccountId,
+		u32,
+		ValueQuery,
+	>;
+
+	/// Feeders suspended from a feed after being slashed.
+	#[pallet::storage]
+	pub type Suspended<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, FeedIdOf<T>, Blake2_128Concat, T::AccountId, ()>;
+
+	/// Largest change a single round may make to a feed's value, relative to the previous value.
+	/// Feeds without an entry are not limited.
+	#[pallet::storage]
+	#[pallet::getter(fn max_change)]
+	pub type MaxChange<T: Config> = StorageMap<_, Blake2_128Concat, FeedIdOf<T>, Perbill>;
+
+	/// Paused feeds. They accept no submissions and keep their last value until resumed.
+	#[pallet::storage]
+	pub type Paused<T: Config> = StorageMap<_, Blake2_128Concat, FeedIdOf<T>, ()>;
+
+	/// Source URLs feeders may query for each feed, in rotation order.
+	#[pallet::storage]
+	#[pallet::getter(fn endpoints)]
+	pub type Endpoints<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		FeedIdOf<T>,
+		BoundedVec<EndpointOf<T>, T::MaxEndpoints>,
+		ValueQuery,
+	>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Feeds registered at genesis as `(name, window, max_age, min_submissions, feeders)`.
+		#[allow(clippy::type_complexity)]
+		pub feeds: Vec<(Vec<u8>, T::BlockNumber, T::BlockNumber, u32, Vec<T::AccountId>)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { feeds: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			assert!(self.feeds.len() as u32 <= T::MaxFeeds::get(), "too many genesis feeds");
+			for (name, window, max_age, min_submissions, feeders) in &self.feeds {
+				let feed: FeedIdOf<T> =
+					name.clone().try_into().expect("feed name exceeds MaxNameLength");
+				assert!(!Feeds::<T>::contains_key(&feed), "duplicate genesis feed");
+				assert!(
+					!window.is_zero() && *min_submissions > 0,
+					"invalid genesis feed parameters"
+				);
+				let feeders: BoundedVec<_, T::MaxFeeders> =
+					feeders.clone().try_into().expect("genesis feed exceeds MaxFeeders");
+				Feeds::<T>::insert(
+					&feed,
+					FeedInfo {
+						feeders,
+						window: *window,
+						max_age: *max_age,
+						min_submissions: *min_submissions,
+					},
+				);
+			}
+			FeedCount::<T>::put(self.feeds.len() as u32);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A feed was registered. [feed]
+		FeedRegistered(FeedIdOf<T>),
+		/// A feed and its value were removed. [feed]
+		FeedRemoved(FeedIdOf<T>),
+		/// An account was allowed to submit to a feed. [feed, feeder]
+		FeederAdded(FeedIdOf<T>, T::AccountId),
+		/// An account may no longer submit to a feed. [feed, feeder]
+		FeederRemoved(FeedIdOf<T>, T::AccountId),
+		/// A feeder submitted a value to the open round. [feed, feeder, value]
+		ValueSubmitted(FeedIdOf<T>, T::AccountId, T::Value),
+		/// A round closed and produced a new value. [feed, value]
+		ValueUpdated(FeedIdOf<T>, T::Value),
+		/// A round closed without enough submissions and was discarded. [feed, submissions]
+		RoundDiscarded(FeedIdOf<T>, u32),
+		/// The bonding and slashing rules were changed.
+		SlashingConfigSet,
+		/// A feeder bonded for a feed. [feed, feeder, bond]
+		FeederBonded(FeedIdOf<T>, T::AccountId, BalanceOf<T>),
+		/// A feeder withdrew its bond for a feed. [feed, feeder, amount]
+		FeederUnbonded(FeedIdOf<T>, T::AccountId, BalanceOf<T>),
+		/// A feeder was slashed and suspended for repeated deviation. [feed, feeder, slashed]
+		FeederSlashed(FeedIdOf<T>, T::AccountId, BalanceOf<T>),
+		/// A suspended feeder may submit again. [feed, feeder]
+		FeederReinstated(FeedIdOf<T>, T::AccountId),
+		/// The limit on how far a round may move a feed's value was changed. [feed, max_change]
+		MaxChangeSet(FeedIdOf<T>, Option<Perbill>),
+		/// A feed was paused by `FeedOrigin`. [feed]
+		FeedPaused(FeedIdOf<T>),
+		/// A paused feed accepts submissions again. [feed]
+		FeedResumed(FeedIdOf<T>),
+		/// A round would have moved a feed's value further than `MaxChange` allows. The value was
+		/// kept and the feed paused. [feed, previous, rejected]
+		CircuitBreakerTripped(FeedIdOf<T>, T::Value, T::Value),
+		/// A URL was added to a feed's endpoint allowlist. [feed, url]
+		EndpointAdded(FeedIdOf<T>, EndpointOf<T>),
+		/// A URL was removed from a feed's endpoint allowlist. [feed, url]
+		EndpointRemoved(FeedIdOf<T>, EndpointOf<T>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// A feed with this name is already registered.
+		FeedExists,
+		/// No feed with this name is registered.
+		UnknownFeed,
+		/// The feed name exceeds `MaxNameLength`.
+		NameTooLong,
+		/// `MaxFeeds` feeds are already registered.
+		TooManyFeeds,
+		/// The window must be at least one block and `min_submissions` at least one.
+		InvalidParameters,
+		/// The account is already a feeder of this feed.
+		AlreadyFeeder,
+		/// The account is not a feeder of this feed.
+		NotFeeder,
+		/// The feed already has `MaxFeeders` feeders.
+		TooManyFeeders,
+		/// The feeder already submitted to the open round.
+		AlreadySubmitted,
+		/// Bonding is not required while no slashing config is set.
+		SlashingDisabled,
+		/// The feeder has not bonded the amount required by the slashing config.
+		NotBonded,
+		/// The feeder has already bonded the required amount.
+		AlreadyBonded,
+		/// The feeder cannot unbond while its submission to the open round is pending.
+		SubmissionPending,
+		/// The feeder is suspended from this feed.
+		FeederSuspended,
+		/// The feeder is not suspended from this feed.
+		NotSuspended,
+		/// The feed is paused.
+		FeedPaused,
+		/// The feed is not paused.
+		FeedNotPaused,
+		/// The URL exceeds `MaxUrlLength`.
+		UrlTooLong,
+		/// The feed already has `MaxEndpoints` endpoints.
+		TooManyEndpoints,
+		/// The URL is already allowed for this feed.
+		EndpointExists,
+		/// The URL is not allowed for this feed.
+		UnknownEndpoint,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let due = Closing::<T>::take(now);
+			let weight = T::WeightInfo::on_initialize(due.len() as u32);
+			for feed in due {
+				if let Some(round) = Rounds::<T>::take(&feed) {
+					Self::close_round(feed, round, now);
+				}
+			}
+			weight
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register a new feed with no feeders.
+		#[pallet::weight(T::WeightInfo::register_feed())]
+		pub fn register_feed(
+			origin: OriginFor<T>,
+			feed: Vec<u8>,
+			window: T::BlockNumber,
+			max_age: T::BlockNumber,
+			min_submissions: u32,
+		) -> DispatchResult {
+			T::FeedOrigin::ensure_origin(origin)?;
+			let feed: FeedIdOf<T> = feed.try_into().map_err(|_| Error::<T>::NameTooLong)?;
+			ensure!(!Feeds::<T>::contains_key(&feed), Error::<T>::FeedExists);
+			ensure!(!window.is_zero() && min_submissions > 0, Error::<T>::InvalidParameters);
+			ensure!(FeedCount::<T>::get() < T::MaxFeeds::get(), Error::<T>::TooManyFeeds);
+
+			Feeds::<T>::insert(
+				&feed,
+				FeedInfo { feeders: Default::default(), window, max_age, min_submissions },
+			);
+			FeedCount::<T>::mutate(|count| *count += 1);
+
+			Self::deposit_event(Event::FeedRegistered(feed));
+			Ok(())
+		}
+
+		/// Remove a feed together with its open round and latest value, returning the feeders'
+		/// bonds.
+		#[pallet::weight(T::WeightInfo::remove_feed())]
+		pub fn remove_feed(origin: OriginFor<T>, feed: FeedIdOf<T>) -> DispatchResult {
+			T::FeedOrigin::ensure_origin(origin)?;
+			let info = Feeds::<T>::get(&feed).ok_or(Error::<T>::UnknownFeed)?;
+
+			for feeder in &info.feeders {
+				Self::release(&feed, feeder);
+			}
+			Feeds::<T>::remove(&feed);
+			FeedCount::<T>::mutate(|count| *count = count.saturating_sub(1));
+			Self::discard_round(&feed);
+			Values::<T>::remove(&feed);
+			History::<T>::remove(&feed);
+			MaxChange::<T>::remove(&feed);
+			Paused::<T>::remove(&feed);
+			Endpoints::<T>::remove(&feed);
+
+			Self::deposit_event(Event::FeedRemoved(feed));
+			Ok(())
+		}
+
+		/// Allow `feeder` to submit to `feed`.
+		#[pallet::weight(T::WeightInfo::add_feeder())]
+		pub fn add_feeder(
+			origin: OriginFor<T>,
+			feed: FeedIdOf<T>,
+			feeder: T::AccountId,
+		) -> DispatchResult {
+			T::FeedOrigin::ensure_origin(origin)?;
+			Feeds::<T>::try_mutate(&feed, |maybe_info| -> DispatchResult {
+				let info = maybe_info.as_mut().ok_or(Error::<T>::UnknownFeed)?;
+				ensure!(!info.feeders.contains(&feeder), Error::<T>::AlreadyFeeder);
+				info.feeders.try_push(feeder.clone()).map_err(|_| Error::<T>::TooManyFeeders)?;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::FeederAdded(feed, feeder));
+			Ok(())
+		}
+
+		/// Revoke `feeder`'s permission to submit to `feed` and return its bond. Submissions
+		/// already made to the open round still count.
+		#[pallet::weight(T::WeightInfo::remove_feeder())]
+		pub fn remove_feeder(
+			origin: OriginFor<T>,
+			feed: FeedIdOf<T>,
+			feeder: T::AccountId,
+		) -> DispatchResult {
+			T::FeedOrigin::ensure_origin(origin)?;
+			Feeds::<T>::try_mutate(&feed, |maybe_info| -> DispatchResult {
+				let info = maybe_info.as_mut().ok_or(Error::<T>::UnknownFeed)?;
+				let index =
+					info.feeders.iter().position(|f| f == &feeder).ok_or(Error::<T>::NotFeeder)?;
+				info.feeders.remove(index);
+				Ok(())
+			})?;
+			Self::release(&feed, &feeder);
+
+			Self::deposit_event(Event::FeederRemoved(feed, feeder));
+			Ok(())
+		}
+
+		/// Submit `value` to `feed`, opening a new round if none is open.
+		#[pallet::weight(T::WeightInfo::submit())]
+		pub fn submit(origin: OriginFor<T>, feed: FeedIdOf<T>, value: T::Value) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let info = Feeds::<T>::get(&feed).ok_or(Error::<T>::UnknownFeed)?;
+			ensure!(info.feeders.contains(&who), Error::<T>::NotFeeder);
+			ensure!(!Paused::<T>::contains_key(&feed), Error::<T>::FeedPaused);
+			ensure!(!Suspended::<T>::contains_key(&feed, &who), Error::<T>::FeederSuspended);
+			if let Some(config) = Self::slashing_config() {
+				let bonded = Bonds::<T>::get(&feed, &who).unwrap_or_default();
+				ensure!(bonded >= config.bond, Error::<T>::NotBonded);
+			}
+
+			Rounds::<T>::try_mutate(&feed, |maybe_round| -> DispatchResult {
+				if maybe_round.is_none() {
+					let closes_at =
+						frame_system::Pallet::<T>::block_number().saturating_add(info.window);
+					Closing::<T>::try_mutate(closes_at, |feeds| feeds.try_push(feed.clone()))
+						.map_err(|_| Error::<T>::TooManyFeeds)?;
+					*maybe_round = Some(Round { closes_at, submissions: Default::default() });
+				}
+				let round = maybe_round.as_mut().expect("opened above if missing; qed");
+				ensure!(
+					!round.submissions.iter().any(|(feeder, _)| feeder == &who),
+					Error::<T>::AlreadySubmitted
+				);
+				round
+					.submissions
+					.try_push((who.clone(), value))
+					.map_err(|_| Error::<T>::TooManyFeeders)?;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::ValueSubmitted(feed, who, value));
+			Ok(())
+		}
+
+		/// Set or clear the bonding and slashing rules. Bonds already taken are kept; feeders
+		/// bonded below a raised `bond` have to top up before submitting again.
+		#[pallet::weight(T::WeightInfo::set_slashing_config())]
+		pub fn set_slashing_config(
+			origin: OriginFor<T>,
+			config: Option<SlashingConfig<BalanceOf<T>>>,
+		) -> DispatchResult {
+			T::FeedOrigin::ensure_origin(origin)?;
+			Slashing::<T>::set(config);
+			Self::deposit_event(Event::SlashingConfigSet);
+			Ok(())
+		}
+
+		/// Bond for `feed`, reserving whatever is missing up to the configured bond. Also used to
+		/// top up a bond after a slash.
+		#[pallet::weight(T::WeightInfo::bond())]
+		pub fn bond(origin: OriginFor<T>, feed: FeedIdOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let config = Self::slashing_config().ok_or(Error::<T>::SlashingDisabled)?;
+			let info = Feeds::<T>::get(&feed).ok_or(Error::<T>::UnknownFeed)?;
+			ensure!(info.feeders.contains(&who), Error::<T>::NotFeeder);
+
+			let bonded = Bonds::<T>::get(&feed, &who).unwrap_or_default();
+			ensure!(bonded < config.bond, Error::<T>::AlreadyBonded);
+			T::Currency::reserve(&who, config.bond.saturating_sub(bonded))?;
+			Bonds::<T>::insert(&feed, &who, config.bond);
+
+			Self::deposit_event(Event::FeederBonded(feed, who, config.bond));
+			Ok(())
+		}
+
+		/// Withdraw the signer's bond for `feed`. Not allowed while the signer's submission to the
+		/// open round can still earn a strike. While a slashing config is set, the feeder has to
+		/// bond again before submitting.
+		#[pallet::weight(T::WeightInfo::unbond())]
+		pub fn unbond(origin: OriginFor<T>, feed: FeedIdOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Bonds::<T>::contains_key(&feed, &who), Error::<T>::NotBonded);
+			let pending = Rounds::<T>::get(&feed)
+				.map_or(false, |round| round.submissions.iter().any(|(feeder, _)| feeder == &who));
+			ensure!(!pending, Error::<T>::SubmissionPending);
+
+			let bonded = Bonds::<T>::take(&feed, &who).unwrap_or_default();
+			T::Currency::unreserve(&who, bonded);
+
+			Self::deposit_event(Event::FeederUnbonded(feed, who, bonded));
+			Ok(())
+		}
+
+		/// Lift the suspension of `feeder` from `feed` and clear its strikes.
+		#[pallet::weight(T::WeightInfo::reinstate())]
+		pub fn reinstate(
+			origin: OriginFor<T>,
+			feed: FeedIdOf<T>,
+			feeder: T::AccountId,
+		) -> DispatchResult {
+			T::FeedOrigin::ensure_origin(origin)?;
+			ensure!(Suspended::<T>::contains_key(&feed, &feeder), Error::<T>::NotSuspended);
+			Suspended::<T>::remove(&feed, &feeder);
+			Strikes::<T>::remove(&feed, &feeder);
+
+			Self::deposit_event(Event::FeederReinstated(feed, feeder));
+			Ok(())
+		}
+
+		/// Limit how far a single round may move the value of `feed`, relative to its previous
+		/// value, or remove the limit. Rounds following a value of zero are not limited.
+		#[pallet::weight(T::WeightInfo::set_max_change())]
+		pub fn set_max_change(
+			origin: OriginFor<T>,
+			feed: FeedIdOf<T>,
+			max_change: Option<Perbill>,
+		) -> DispatchResult {
+			T::FeedOrigin::ensure_origin(origin)?;
+			ensure!(Feeds::<T>::contains_key(&feed), Error::<T>::UnknownFeed);
+			match max_change {
+				Some(limit) => MaxChange::<T>::insert(&feed, limit),
+				None => MaxChange::<T>::remove(&feed),
+			}
+
+			Self::deposit_event(Event::MaxChangeSet(feed, max_change));
+			Ok(())
+		}
+
+		/// Pause `feed`, discarding its open round. The feed keeps its last value, which still
+		/// becomes stale after `max_age`.
+		#[pallet::weight(T::WeightInfo::pause_feed())]
+		pub fn pause_feed(origin: OriginFor<T>, feed: FeedIdOf<T>) -> DispatchResult {
+			T::FeedOrigin::ensure_origin(origin)?;
+			ensure!(Feeds::<T>::contains_key(&feed), Error::<T>::UnknownFeed);
+			ensure!(!Paused::<T>::contains_key(&feed), Error::<T>::FeedPaused);
+			Self::pause(&feed);
+
+			Self::deposit_event(Event::FeedPaused(feed));
+			Ok(())
+		}
+
+		/// Resume a paused feed. If the feed was paused by its circuit breaker, the next round is
+		/// checked against the same previous value, so a genuine large move needs `MaxChange` to be
+		/// raised or removed first.
+		#[pallet::weight(T::WeightInfo::resume_feed())]
+		pub fn resume_feed(origin: OriginFor<T>, feed: FeedIdOf<T>) -> DispatchResult {
+			T::FeedOrigin::ensure_origin(origin)?;
+			ensure!(Paused::<T>::contains_key(&feed), Error::<T>::FeedNotPaused);
+			Paused::<T>::remove(&feed);
+
+			Self::deposit_event(Event::FeedResumed(feed));
+			Ok(())
+		}
+
+		/// Allow feeders to query `url` for `feed`. New endpoints join the end of the rotation.
+		#[pallet::weight(T::WeightInfo::add_endpoint())]
+		pub fn add_endpoint(
+			origin: OriginFor<T>,
+			feed: FeedIdOf<T>,
+			url: Vec<u8>,
+		) -> DispatchResult {
+			T::FeedOrigin::ensure_origin(origin)?;
+			ensure!(Feeds::<T>::contains_key(&feed), Error::<T>::UnknownFeed);
+			let url: EndpointOf<T> = url.try_into().map_err(|_| Error::<T>::UrlTooLong)?;
+
+			Endpoints::<T>::try_mutate(&feed, |endpoints| -> DispatchResult {
+				ensure!(!endpoints.contains(&url), Error::<T>::EndpointExists);
+				endpoints.try_push(url.clone()).map_err(|_| Error::<T>::TooManyEndpoints)?;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::EndpointAdded(feed, url));
+			Ok(())
+		}
+
+		/// Remove `url` from the endpoints allowed for `feed`.
+		#[pallet::weight(T::WeightInfo::remove_endpoint())]
+		pub fn remove_endpoint(
+			origin: OriginFor<T>,
+			feed: FeedIdOf<T>,
+			url: Vec<u8>,
+		) -> DispatchResult {
+			T::FeedOrigin::ensure_origin(origin)?;
+			let url: EndpointOf<T> = url.try_into().map_err(|_| Error::<T>::UnknownEndpoint)?;
+
+			Endpoints::<T>::try_mutate(&feed, |endpoints| -> DispatchResult {
+				let index =
+					endpoints.iter().position(|e| e == &url).ok_or(Error::<T>::UnknownEndpoint)?;
+				endpoints.remove(index);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::EndpointRemoved(feed, url));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The endpoint feeders should query for `feed` at block `now`. Feeders move to the next
+		/// allowed endpoint every block, so one failing source is retried only once per rotation.
+		pub fn endpoint_for(feed: &FeedIdOf<T>, now: T::BlockNumber) -> Option<EndpointOf<T>> {
+			let endpoints = Endpoints::<T>::get(feed);
+			if endpoints.is_empty() {
+				return None
+			}
+			let index = now.saturated_into::<u32>() % endpoints.len() as u32;
+			endpoints.get(index as usize).cloned()
+		}
+
+		/// Aggregate a closed round into the feed's value, or discard it if too few feeders
+		/// took part.
+		fn close_round(feed: FeedIdOf<T>, round: Round<T>, now: T::BlockNumber) {
+			let count = round.submissions.len() as u32;
+			let info = match Feeds::<T>::get(&feed) {
+				Some(info) if count >= info.min_submissions => info,
+				_ => {
+					Self::deposit_event(Event::RoundDiscarded(feed, count));
+					return
+				},
+			};
+
+			let mut values: Vec<T::Value> = round.submissions.iter().map(|(_, v)| *v).collect();
+			let value = Self::median(&mut values);
+			if let Some(previous) = Self::breaches_max_change(&feed, value) {
+				Self::pause(&feed);
+				Self::deposit_event(Event::CircuitBreakerTripped(feed, previous, value));
+				return
+			}
+			if let Some(config) = Self::slashing_config() {
+				Self::record_deviations(&feed, &info, &round, value, &config);
+			}
+			let current = TimestampedValue { value, updated_at: now };
+			History::<T>::mutate(&feed, |history| {
+				if !history.is_empty() && history.len() as u32 >= T::MaxHistory::get() {
+					history.remove(0);
+				}
+				// Only fails if `MaxHistory` is zero, in which case no history is kept.
+				let _ = history.try_push(current.clone());
+			});
+			Values::<T>::insert(&feed, current);
+			Self::deposit_event(Event::ValueUpdated(feed, value));
+		}
+
+		/// The feed's previous value if moving it to `value` exceeds the feed's `MaxChange`. A
+		/// previous value of zero is not checked, as any move away from it would trip the limit.
+		fn breaches_max_change(feed: &FeedIdOf<T>, value: T::Value) -> Option<T::Value> {
+			let max_change = MaxChange::<T>::get(feed)?;
+			let previous = Values::<T>::get(feed)?.value;
+			if previous.is_zero() {
+				return None
+			}
+			let change = if value > previous { value - previous } else { previous - value };
+			if change > max_change.mul_floor(previous) {
+				Some(previous)
+			} else {
+				None
+			}
+		}
+
+		/// Stop `feed` from accepting submissions and drop its open round.
+		fn pause(feed: &FeedIdOf<T>) {
+			Paused::<T>::insert(feed, ());
+			Self::discard_round(feed);
+		}
+
+		/// Drop the open round of `feed` without aggregating it.
+		fn discard_round(feed: &FeedIdOf<T>) {
+			if let Some(round) = Rounds::<T>::take(feed) {
+				Closing::<T>::mutate(round.closes_at, |feeds| {
+					if let Some(index) = feeds.iter().position(|f| f == feed) {
+						feeds.remove(index);
+					}
+				});
+			}
+		}
+
+		/// Update the strikes of every feeder in `round` against the aggregated `median`, slashing
+		/// and suspending those that reach `max_strikes`. Accounts removed from the feed since
+		/// submitting are skipped; their bond has already been returned.
+		fn record_deviations(
+			feed: &FeedIdOf<T>,
+			info: &FeedInfo<T>,
+			round: &Round<T>,
+			median: T::Value,
+			config: &SlashingConfig<BalanceOf<T>>,
+		) {
+			let tolerance = config.tolerance.mul_floor(median);
+			for (feeder, submitted) in round.submissions.iter() {
+				if !info.feeders.contains(feeder) {
+					continue
+				}
+				let deviation =
+					if *submitted > median { *submitted - median } else { median - *submitted };
+				if deviation <= tolerance {
+					Strikes::<T>::remove(feed, feeder);
+					continue
+				}
+				let strikes = Strikes::<T>::mutate(feed, feeder, |strikes| {
+					*strikes = strikes.saturating_add(1);
+					*strikes
+				});
+				if strikes >= config.max_strikes {
+					Self::slash(feed, feeder, config.slash_fraction);
+				}
+			}
+		}
+
+		/// Slash `fraction` of the feeder's bond to `Slash` and suspend it from the feed.
+		fn slash(feed: &FeedIdOf<T>, feeder: &T::AccountId, fraction: Perbill) {
+			let bonded = Bonds::<T>::get(feed, feeder).unwrap_or_default();
+			let (imbalance, _) = T::Currency::slash_reserved(feeder, fraction * bonded);
+			let slashed = imbalance.peek();
+			T::Slash::on_unbalanced(imbalance);
+			if !bonded.is_zero() {
+				Bonds::<T>::insert(feed, feeder, bonded.saturating_sub(slashed));
+			}
+			Strikes::<T>::remove(feed, feeder);
+			Suspended::<T>::insert(feed, feeder, ());
+
+			Self::deposit_event(Event::FeederSlashed(feed.clone(), feeder.clone(), slashed));
+		}
+
+		/// Return the feeder's bond and forget its strikes and suspension.
+		fn release(feed: &FeedIdOf<T>, feeder: &T::AccountId) {
+			if let Some(bonded) = Bonds::<T>::take(feed, feeder) {
+				T::Currency::unreserve(feeder, bonded);
+			}
+			Strikes::<T>::remove(feed, feeder);
+			Suspended::<T>::remove(feed, feeder);
+		}
+
+		/// Median of a non-empty set of values. For an even count the two middle values are
+		/// averaged, rounding down.
+		pub(crate) fn median(values: &mut [T::Value]) -> T::Value {
+			values.sort();
+			let mid = values.len() / 2;
+			if values.len() % 2 == 1 {
+				values[mid]
+			} else {
+				let (low, high) = (values[mid - 1], values[mid]);
+				low + (high - low) / 2u32.into()
+			}
+		}
+	}
+
+	impl<T: Config> FeedProvider<FeedIdOf<T>, T::Value> for Pallet<T> {
+		fn latest(feed: &FeedIdOf<T>) -> Option<T::Value> {
+			let info = Feeds::<T>::get(feed)?;
+			let current = Values::<T>::get(feed)?;
+			let age = frame_system::Pallet::<T>::block_number().saturating_sub(current.updated_at);
+			if age > info.max_age {
+				return None
+			}
+			Some(current.value)
+		}
+	}
+	impl<T: Config> FeedHistory<FeedIdOf<T>, T::Value, T::BlockNumber> for Pallet<T> {
+		fn twap(feed: &FeedIdOf<T>, window: T::BlockNumber) -> Option<T::Value> {
+			// Refuse to average over a feed that is no longer being updated.
+			let latest = Self::latest(feed)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			let start = now.saturating_sub(window);
+
+			let mut weighted = T::Value::zero();
+			let mut total = 0u32;
+			// Walk back from the newest value; each one was current until the next one replaced
+			// it, or until now for the newest.
+			let mut end = now;
+			for observation in History::<T>::get(feed).iter().rev() {
+				let from = observation.updated_at.max(start);
+				if end > from {
+					let blocks: u32 = end.saturating_sub(from).saturated_into();
+					let contribution = observation.value.saturating_mul(blocks.into());
+					weighted = weighted.saturating_add(contribution);
+					total = total.saturating_add(blocks);
+				}
+				if observation.updated_at <= start {
+					break
+				}
+				end = observation.updated_at;
+			}
+
+			if total == 0 {
+				return Some(latest)
+			}
+			Some(weighted / total.into())
+		}
+	}
+}
@@ -0,0 +1,511 @@
+use crate::{
+	mock::*, Closing, Error, FeedCount, FeedHistory, FeedIdOf, FeedProvider, SlashingConfig,
+};
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use sp_runtime::Perbill;
+
+fn feed() -> FeedIdOf<Test> {
+	b"DOT/USD".to_vec().try_into().unwrap()
+}
+
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		Oracle::on_initialize(System::block_number());
+	}
+}
+
+/// Register `DOT/USD` with a two block window, a staleness limit of ten blocks, a quorum of two
+/// and feeders 1, 2 and 3.
+fn setup_feed() {
+	assert_ok!(Oracle::register_feed(Origin::root(), feed().to_vec(), 2, 10, 2));
+	for feeder in 1..=3 {
+		assert_ok!(Oracle::add_feeder(Origin::root(), feed(), feeder));
+	}
+}
+
+#[test]
+fn feeds_are_managed_by_feed_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Oracle::register_feed(Origin::signed(1), feed().to_vec(), 2, 10, 2),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Oracle::register_feed(Origin::root(), feed().to_vec(), 0, 10, 2),
+			Error::<Test>::InvalidParameters
+		);
+		setup_feed();
+		assert_noop!(
+			Oracle::register_feed(Origin::root(), feed().to_vec(), 2, 10, 2),
+			Error::<Test>::FeedExists
+		);
+		assert_noop!(Oracle::add_feeder(Origin::root(), feed(), 1), Error::<Test>::AlreadyFeeder);
+		assert_ok!(Oracle::add_feeder(Origin::root(), feed(), 4));
+		assert_noop!(Oracle::add_feeder(Origin::root(), feed(), 5), Error::<Test>::TooManyFeeders);
+
+		assert_ok!(Oracle::remove_feeder(Origin::root(), feed(), 4));
+		assert_noop!(Oracle::submit(Origin::signed(4), feed(), 1), Error::<Test>::NotFeeder);
+
+		assert_ok!(Oracle::remove_feed(Origin::root(), feed()));
+		assert!(Oracle::feed(feed()).is_none());
+	});
+}
+
+#[test]
+fn round_closes_with_median() {
+	new_test_ext().execute_with(|| {
+		setup_feed();
+		assert_ok!(Oracle::submit(Origin::signed(1), feed(), 100));
+		assert_ok!(Oracle::submit(Origin::signed(2), feed(), 300));
+		assert_noop!(
+			Oracle::submit(Origin::signed(2), feed(), 300),
+			Error::<Test>::AlreadySubmitted
+		);
+		assert_ok!(Oracle::submit(Origin::signed(3), feed(), 110));
+		assert_eq!(Oracle::round(feed()).unwrap().closes_at, 3);
+
+		run_to_block(2);
+		assert_eq!(Oracle::latest(&feed()), None);
+
+		run_to_block(3);
+		assert!(Oracle::round(feed()).is_none());
+		assert_eq!(Oracle::latest(&feed()), Some(110));
+		assert_eq!(Oracle::value(feed()).unwrap().updated_at, 3);
+	});
+}
+
+#[test]
+fn median_averages_even_counts() {
+	new_test_ext().execute_with(|| {
+		setup_feed();
+		assert_ok!(Oracle::submit(Origin::signed(1), feed(), 100));
+		assert_ok!(Oracle::submit(Origin::signed(2), feed(), 105));
+
+		run_to_block(3);
+		assert_eq!(Oracle::latest(&feed()), Some(102));
+	});
+}
+
+#[test]
+fn round_without_quorum_is_discarded() {
+	new_test_ext().execute_with(|| {
+		setup_feed();
+		assert_ok!(Oracle::submit(Origin::signed(1), feed(), 100));
+
+		run_to_block(3);
+		assert!(Oracle::round(feed()).is_none());
+		assert!(Oracle::value(feed()).is_none());
+		System::assert_last_event(crate::Event::RoundDiscarded(feed(), 1).into());
+	});
+}
+
+#[test]
+fn stale_values_are_hidden() {
+	new_test_ext().execute_with(|| {
+		setup_feed();
+		assert_ok!(Oracle::submit(Origin::signed(1), feed(), 100));
+		assert_ok!(Oracle::submit(Origin::signed(2), feed(), 100));
+
+		run_to_block(13);
+		assert_eq!(Oracle::latest(&feed()), Some(100));
+		run_to_block(14);
+		assert_eq!(Oracle::latest(&feed()), None);
+		// The raw value is kept until a new round replaces it.
+		assert!(Oracle::value(feed()).is_some());
+	});
+}
+
+/// Close a round for `DOT/USD` at `value`, with the round opened at the current block.
+fn report(value: u64) {
+	assert_ok!(Oracle::submit(Origin::signed(1), feed(), value));
+	assert_ok!(Oracle::submit(Origin::signed(2), feed(), value));
+	run_to_block(System::block_number() + 2);
+}
+
+#[test]
+fn history_keeps_latest_values() {
+	new_test_ext().execute_with(|| {
+		setup_feed();
+		for value in [100, 200, 300, 400] {
+			report(value);
+		}
+
+		let history: Vec<_> =
+			Oracle::history(feed()).iter().map(|o| (o.value, o.updated_at)).collect();
+		assert_eq!(history, vec![(200, 5), (300, 7), (400, 9)]);
+
+		assert_ok!(Oracle::remove_feed(Origin::root(), feed()));
+		assert!(Oracle::history(feed()).is_empty());
+	});
+}
+
+#[test]
+fn twap_weights_values_by_blocks_current() {
+	new_test_ext().execute_with(|| {
+		setup_feed();
+		// 100 is current from block 3, 400 from block 5.
+		report(100);
+		report(400);
+		run_to_block(8);
+
+		// Blocks 5..8 at 400 only.
+		assert_eq!(Oracle::twap(&feed(), 3), Some(400));
+		// Blocks 3..5 at 100 and 5..8 at 400.
+		assert_eq!(Oracle::twap(&feed(), 5), Some((2 * 100 + 3 * 400) / 5));
+		// Blocks before the first value do not count.
+		assert_eq!(Oracle::twap(&feed(), 100), Some((2 * 100 + 3 * 400) / 5));
+		// A window of zero is the latest value.
+		assert_eq!(Oracle::twap(&feed(), 0), Some(400));
+	});
+}
+
+#[test]
+fn twap_of_stale_feed_is_none() {
+	new_test_ext().execute_with(|| {
+		setup_feed();
+		report(100);
+		run_to_block(14);
+		assert_eq!(Oracle::twap(&feed(), 5), None);
+	});
+}
+
+/// Require a bond of 100, allow 10% deviation and slash half the bond after two strikes.
+fn enable_slashing() {
+	assert_ok!(Oracle::set_slashing_config(
+		Origin::root(),
+		Some(SlashingConfig {
+			bond: 100,
+			tolerance: Perbill::from_percent(10),
+			max_strikes: 2,
+			slash_fraction: Perbill::from_percent(50),
+		})
+	));
+}
+
+/// Run a round in which feeders 1 and 2 report 100 and feeder 3 reports `outlier`.
+fn round_with_outlier(outlier: u64) {
+	assert_ok!(Oracle::submit(Origin::signed(1), feed(), 100));
+	assert_ok!(Oracle::submit(Origin::signed(2), feed(), 100));
+	assert_ok!(Oracle::submit(Origin::signed(3), feed(), outlier));
+	run_to_block(System::block_number() + 2);
+}
+
+#[test]
+fn feeders_must_bond_once_slashing_is_enabled() {
+	new_test_ext().execute_with(|| {
+		setup_feed();
+		assert_noop!(Oracle::bond(Origin::signed(1), feed()), Error::<Test>::SlashingDisabled);
+
+		enable_slashing();
+		assert_noop!(Oracle::submit(Origin::signed(1), feed(), 100), Error::<Test>::NotBonded);
+		assert_noop!(Oracle::bond(Origin::signed(4), feed()), Error::<Test>::NotFeeder);
+
+		assert_ok!(Oracle::bond(Origin::signed(1), feed()));
+		assert_eq!(Balances::reserved_balance(1), 100);
+		assert_noop!(Oracle::bond(Origin::signed(1), feed()), Error::<Test>::AlreadyBonded);
+		assert_ok!(Oracle::submit(Origin::signed(1), feed(), 100));
+
+		assert_ok!(Oracle::remove_feeder(Origin::root(), feed(), 1));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Oracle::bond_of(feed(), 1), None);
+	});
+}
+
+#[test]
+fn repeated_deviation_is_slashed_and_suspended() {
+	new_test_ext().execute_with(|| {
+		setup_feed();
+		enable_slashing();
+		for feeder in 1..=3 {
+			assert_ok!(Oracle::bond(Origin::signed(feeder), feed()));
+		}
+
+		// Within tolerance: no strike.
+		round_with_outlier(110);
+		assert_eq!(Oracle::strikes(feed(), 3), 0);
+
+		round_with_outlier(150);
+		assert_eq!(Oracle::strikes(feed(), 3), 1);
+		assert_eq!(Balances::reserved_balance(3), 100);
+
+		round_with_outlier(150);
+		assert_eq!(Oracle::strikes(feed(), 3), 0);
+		assert_eq!(Balances::reserved_balance(3), 50);
+		assert_eq!(Balances::free_balance(3), 900);
+		assert_eq!(Oracle::bond_of(feed(), 3), Some(50));
+		System::assert_has_event(crate::Event::FeederSlashed(feed(), 3, 50).into());
+
+		assert_noop!(
+			Oracle::submit(Origin::signed(3), feed(), 100),
+			Error::<Test>::FeederSuspended
+		);
+		// The honest feeders are untouched.
+		assert_eq!(Balances::reserved_balance(1), 100);
+	});
+}
+
+#[test]
+fn reinstated_feeder_tops_up_bond() {
+	new_test_ext().execute_with(|| {
+		setup_feed();
+		enable_slashing();
+		for feeder in 1..=3 {
+			assert_ok!(Oracle::bond(Origin::signed(feeder), feed()));
+		}
+		round_with_outlier(150);
+		round_with_outlier(150);
+
+		assert_noop!(Oracle::reinstate(Origin::root(), feed(), 1), Error::<Test>::NotSuspended);
+		assert_ok!(Oracle::reinstate(Origin::root(), feed(), 3));
+		assert_noop!(Oracle::submit(Origin::signed(3), feed(), 100), Error::<Test>::NotBonded);
+
+		assert_ok!(Oracle::bond(Origin::signed(3), feed()));
+		assert_eq!(Balances::reserved_balance(3), 100);
+		assert_ok!(Oracle::submit(Origin::signed(3), feed(), 100));
+	});
+}
+
+#[test]
+fn removing_feed_returns_bonds() {
+	new_test_ext().execute_with(|| {
+		setup_feed();
+		enable_slashing();
+		assert_ok!(Oracle::bond(Origin::signed(1), feed()));
+		assert_ok!(Oracle::bond(Origin::signed(2), feed()));
+
+		assert_ok!(Oracle::remove_feed(Origin::root(), feed()));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 0);
+	});
+}
+
+/// Run a round in which feeders 1 and 2 both report `value`.
+fn agreed_round(value: u64) {
+	assert_ok!(Oracle::submit(Origin::signed(1), feed(), value));
+	assert_ok!(Oracle::submit(Origin::signed(2), feed(), value));
+	run_to_block(System::block_number() + 2);
+}
+
+#[test]
+fn paused_feed_keeps_its_value() {
+	new_test_ext().execute_with(|| {
+		setup_feed();
+		agreed_round(100);
+
+		assert_noop!(
+			Oracle::pause_feed(Origin::signed(1), feed()),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(Oracle::resume_feed(Origin::root(), feed()), Error::<Test>::FeedNotPaused);
+		assert_ok!(Oracle::submit(Origin::signed(1), feed(), 500));
+		assert_ok!(Oracle::pause_feed(Origin::root(), feed()));
+		assert_noop!(Oracle::pause_feed(Origin::root(), feed()), Error::<Test>::FeedPaused);
+
+		// The open round was dropped and no new one can be started.
+		assert!(Oracle::round(feed()).is_none());
+		assert_noop!(Oracle::submit(Origin::signed(2), feed(), 500), Error::<Test>::FeedPaused);
+		assert_eq!(Oracle::latest(&feed()), Some(100));
+
+		assert_ok!(Oracle::resume_feed(Origin::root(), feed()));
+		agreed_round(120);
+		assert_eq!(Oracle::latest(&feed()), Some(120));
+	});
+}
+
+#[test]
+fn circuit_breaker_freezes_feed() {
+	new_test_ext().execute_with(|| {
+		setup_feed();
+		assert_ok!(Oracle::set_max_change(Origin::root(), feed(), Some(Perbill::from_percent(10))));
+		// Without a previous value there is nothing to compare against.
+		agreed_round(100);
+		assert_eq!(Oracle::latest(&feed()), Some(100));
+
+		agreed_round(110);
+		assert_eq!(Oracle::latest(&feed()), Some(110));
+
+		agreed_round(200);
+		assert_eq!(Oracle::latest(&feed()), Some(110));
+		System::assert_has_event(crate::Event::CircuitBreakerTripped(feed(), 110, 200).into());
+		assert_noop!(Oracle::submit(Origin::signed(1), feed(), 200), Error::<Test>::FeedPaused);
+
+		// Governance accepts the move by lifting the limit before resuming.
+		assert_ok!(Oracle::set_max_change(Origin::root(), feed(), None));
+		assert_ok!(Oracle::resume_feed(Origin::root(), feed()));
+		agreed_round(200);
+		assert_eq!(Oracle::latest(&feed()), Some(200));
+	});
+}
+
+#[test]
+fn circuit_breaker_skips_zero_previous_value() {
+	new_test_ext().execute_with(|| {
+		setup_feed();
+		assert_ok!(Oracle::set_max_change(Origin::root(), feed(), Some(Perbill::from_percent(10))));
+		agreed_round(0);
+		assert_eq!(Oracle::latest(&feed()), Some(0));
+
+		// Any move away from zero exceeds a relative limit, so it is not checked.
+		agreed_round(100);
+		assert_eq!(Oracle::latest(&feed()), Some(100));
+
+		agreed_round(200);
+		assert_eq!(Oracle::latest(&feed()), Some(100));
+	});
+}
+
+#[test]
+fn endpoints_are_managed_by_feed_origin() {
+	new_test_ext().execute_with(|| {
+		let url = b"https://api.example.com/dot".to_vec();
+		assert_noop!(
+			Oracle::add_endpoint(Origin::root(), feed(), url.clone()),
+			Error::<Test>::UnknownFeed
+		);
+		setup_feed();
+		assert_noop!(
+			Oracle::add_endpoint(Origin::signed(1), feed(), url.clone()),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Oracle::add_endpoint(Origin::root(), feed(), vec![b'a'; 33]),
+			Error::<Test>::UrlTooLong
+		);
+
+		assert_ok!(Oracle::add_endpoint(Origin::root(), feed(), url.clone()));
+		assert_noop!(
+			Oracle::add_endpoint(Origin::root(), feed(), url.clone()),
+			Error::<Test>::EndpointExists
+		);
+		assert_ok!(Oracle::add_endpoint(Origin::root(), feed(), b"https://b.example.com".to_vec()));
+		assert_noop!(
+			Oracle::add_endpoint(Origin::root(), feed(), b"https://c.example.com".to_vec()),
+			Error::<Test>::TooManyEndpoints
+		);
+
+		assert_ok!(Oracle::remove_endpoint(Origin::root(), feed(), url.clone()));
+		assert_noop!(
+			Oracle::remove_endpoint(Origin::root(), feed(), url),
+			Error::<Test>::UnknownEndpoint
+		);
+		assert_eq!(Oracle::endpoints(feed()).len(), 1);
+
+		assert_ok!(Oracle::remove_feed(Origin::root(), feed()));
+		assert!(Oracle::endpoints(feed()).is_empty());
+	});
+}
+
+#[test]
+fn endpoints_rotate_every_block() {
+	new_test_ext().execute_with(|| {
+		setup_feed();
+		assert_eq!(Oracle::endpoint_for(&feed(), 1), None);
+
+		let (a, b) = (b"https://a.example.com".to_vec(), b"https://b.example.com".to_vec());
+		assert_ok!(Oracle::add_endpoint(Origin::root(), feed(), a.clone()));
+		assert_ok!(Oracle::add_endpoint(Origin::root(), feed(), b.clone()));
+
+		assert_eq!(Oracle::endpoint_for(&feed(), 4).unwrap().to_vec(), a);
+		assert_eq!(Oracle::endpoint_for(&feed(), 5).unwrap().to_vec(), b);
+		assert_eq!(Oracle::endpoint_for(&feed(), 6).unwrap().to_vec(), a);
+	});
+}
+
+#[test]
+fn number_of_feeds_is_bounded() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Oracle::register_feed(Origin::root(), b"A".to_vec(), 2, 10, 1));
+		assert_ok!(Oracle::register_feed(Origin::root(), b"B".to_vec(), 2, 10, 1));
+		assert_noop!(
+			Oracle::register_feed(Origin::root(), b"C".to_vec(), 2, 10, 1),
+			Error::<Test>::TooManyFeeds
+		);
+
+		assert_ok!(Oracle::remove_feed(Origin::root(), b"A".to_vec().try_into().unwrap()));
+		assert_ok!(Oracle::register_feed(Origin::root(), b"C".to_vec(), 2, 10, 1));
+	});
+}
+
+#[test]
+fn genesis_registers_feeds() {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	crate::GenesisConfig::<Test> { feeds: vec![(feed().to_vec(), 2, 10, 1, vec![1, 2])] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	sp_io::TestExternalities::new(t).execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(Oracle::feed(feed()).unwrap().feeders.into_inner(), vec![1, 2]);
+		assert_eq!(FeedCount::<Test>::get(), 1);
+		assert_ok!(Oracle::submit(Origin::signed(1), feed(), 100));
+		assert_noop!(Oracle::submit(Origin::signed(3), feed(), 100), Error::<Test>::NotFeeder);
+	});
+}
+
+#[test]
+fn rounds_are_indexed_by_closing_block() {
+	new_test_ext().execute_with(|| {
+		setup_feed();
+		assert_ok!(Oracle::submit(Origin::signed(1), feed(), 100));
+		assert_eq!(Closing::<Test>::get(3).into_inner(), vec![feed()]);
+
+		// Pausing drops the round from the index as well.
+		assert_ok!(Oracle::pause_feed(Origin::root(), feed()));
+		assert!(Closing::<Test>::get(3).is_empty());
+
+		assert_ok!(Oracle::resume_feed(Origin::root(), feed()));
+		assert_ok!(Oracle::submit(Origin::signed(1), feed(), 100));
+		assert_ok!(Oracle::submit(Origin::signed(2), feed(), 100));
+		run_to_block(3);
+		assert!(!Closing::<Test>::contains_key(3));
+		assert_eq!(Oracle::latest(&feed()), Some(100));
+	});
+}
+
+#[test]
+fn feeders_can_unbond_without_pending_submission() {
+	new_test_ext().execute_with(|| {
+		setup_feed();
+		enable_slashing();
+		assert_noop!(Oracle::unbond(Origin::signed(1), feed()), Error::<Test>::NotBonded);
+		assert_ok!(Oracle::bond(Origin::signed(1), feed()));
+		assert_ok!(Oracle::bond(Origin::signed(2), feed()));
+
+		assert_ok!(Oracle::submit(Origin::signed(1), feed(), 100));
+		assert_noop!(Oracle::unbond(Origin::signed(1), feed()), Error::<Test>::SubmissionPending);
+		assert_ok!(Oracle::submit(Origin::signed(2), feed(), 100));
+		run_to_block(3);
+
+		assert_ok!(Oracle::unbond(Origin::signed(1), feed()));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		System::assert_has_event(crate::Event::FeederUnbonded(feed(), 1, 100).into());
+		assert_noop!(Oracle::submit(Origin::signed(1), feed(), 100), Error::<Test>::NotBonded);
+
+		// Once slashing is disabled, bonds are no longer needed and can be withdrawn.
+		assert_ok!(Oracle::set_slashing_config(Origin::root(), None));
+		assert_ok!(Oracle::unbond(Origin::signed(2), feed()));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_ok!(Oracle::submit(Origin::signed(1), feed(), 100));
+	});
+}
+
+#[test]
+fn removed_feeders_are_not_struck() {
+	new_test_ext().execute_with(|| {
+		setup_feed();
+		enable_slashing();
+		for feeder in 1..=3 {
+			assert_ok!(Oracle::bond(Origin::signed(feeder), feed()));
+		}
+		assert_ok!(Oracle::submit(Origin::signed(1), feed(), 100));
+		assert_ok!(Oracle::submit(Origin::signed(2), feed(), 100));
+		assert_ok!(Oracle::submit(Origin::signed(3), feed(), 150));
+		assert_ok!(Oracle::remove_feeder(Origin::root(), feed(), 3));
+		run_to_block(3);
+
+		// The submission still counted towards the value, but the account is left alone.
+		assert_eq!(Oracle::latest(&feed()), Some(100));
+		assert!(!crate::Strikes::<Test>::contains_key(feed(), 3));
+		assert!(!crate::Suspended::<Test>::contains_key(feed(), 3));
+	});
+}
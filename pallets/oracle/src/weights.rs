@@ -0,0 +1,321 @@
+//! Weights for pallet_oracle.
+//!
+//! The values below are hand-written estimates, not benchmark output. The pallet has not been
+//! benchmarked yet; replace this file with generated weights by running:
+//!
+//! ```sh
+//! ./target/release/node-template benchmark pallet \
+//!     --chain dev \
+//!     --pallet pallet_oracle \
+//!     --extrinsic '*' \
+//!     --steps 50 \
+//!     --repeat 20 \
+//!     --output pallets/oracle/src/weights.rs
+//! ```
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_oracle.
+pub trait WeightInfo {
+	fn register_feed() -> Weight;
+	fn remove_feed() -> Weight;
+	fn add_feeder() -> Weight;
+	fn remove_feeder() -> Weight;
+	fn submit() -> Weight;
+	fn set_slashing_config() -> Weight;
+	fn bond() -> Weight;
+	fn unbond() -> Weight;
+	fn reinstate() -> Weight;
+	fn set_max_change() -> Weight;
+	fn pause_feed() -> Weight;
+	fn resume_feed() -> Weight;
+	fn add_endpoint() -> Weight;
+	fn remove_endpoint() -> Weight;
+	fn on_initialize(r: u32, ) -> Weight;
+}
+
+/// Hand-written placeholder weights for pallet_oracle.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Oracle Feeds (r:1 w:1)
+	// Storage: Oracle FeedCount (r:1 w:1)
+	fn register_feed() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Oracle Feeds (r:1 w:1)
+	// Storage: Oracle Bonds (r:16 w:16)
+	// Storage: System Account (r:16 w:16)
+	// Storage: Oracle Strikes (r:0 w:16)
+	// Storage: Oracle Suspended (r:0 w:16)
+	// Storage: Oracle FeedCount (r:1 w:1)
+	// Storage: Oracle Rounds (r:1 w:1)
+	// Storage: Oracle Closing (r:1 w:1)
+	// Storage: Oracle Values (r:0 w:1)
+	// Storage: Oracle History (r:0 w:1)
+	// Storage: Oracle MaxChange (r:0 w:1)
+	// Storage: Oracle Paused (r:0 w:1)
+	// Storage: Oracle Endpoints (r:0 w:1)
+	fn remove_feed() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(36 as Weight))
+			.saturating_add(T::DbWeight::get().writes(73 as Weight))
+	}
+	// Storage: Oracle Feeds (r:1 w:1)
+	fn add_feeder() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Oracle Feeds (r:1 w:1)
+	// Storage: Oracle Bonds (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Oracle Strikes (r:0 w:1)
+	// Storage: Oracle Suspended (r:0 w:1)
+	fn remove_feeder() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Oracle Feeds (r:1 w:0)
+	// Storage: Oracle Paused (r:1 w:0)
+	// Storage: Oracle Suspended (r:1 w:0)
+	// Storage: Oracle Slashing (r:1 w:0)
+	// Storage: Oracle Bonds (r:1 w:0)
+	// Storage: Oracle Rounds (r:1 w:1)
+	// Storage: Oracle Closing (r:1 w:1)
+	fn submit() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Oracle Slashing (r:0 w:1)
+	fn set_slashing_config() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Oracle Slashing (r:1 w:0)
+	// Storage: Oracle Feeds (r:1 w:0)
+	// Storage: Oracle Bonds (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn bond() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Oracle Bonds (r:1 w:1)
+	// Storage: Oracle Rounds (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	fn unbond() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Oracle Suspended (r:1 w:1)
+	// Storage: Oracle Strikes (r:0 w:1)
+	fn reinstate() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Oracle Feeds (r:1 w:0)
+	// Storage: Oracle MaxChange (r:0 w:1)
+	fn set_max_change() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Oracle Feeds (r:1 w:0)
+	// Storage: Oracle Paused (r:1 w:1)
+	// Storage: Oracle Rounds (r:1 w:1)
+	// Storage: Oracle Closing (r:1 w:1)
+	fn pause_feed() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Oracle Paused (r:1 w:1)
+	fn resume_feed() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Oracle Feeds (r:1 w:0)
+	// Storage: Oracle Endpoints (r:1 w:1)
+	fn add_endpoint() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Oracle Endpoints (r:1 w:1)
+	fn remove_endpoint() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Oracle Closing (r:1 w:1)
+	// Storage: Oracle Rounds (r:1 w:1)
+	// Storage: Oracle Feeds (r:1 w:0)
+	// Storage: Oracle Slashing (r:1 w:0)
+	// Storage: Oracle MaxChange (r:1 w:0)
+	// Storage: Oracle Values (r:1 w:1)
+	// Storage: Oracle Strikes (r:16 w:16)
+	// Storage: Oracle Bonds (r:16 w:16)
+	// Storage: System Account (r:16 w:16)
+	// Storage: Oracle Suspended (r:0 w:16)
+	// Storage: Oracle History (r:1 w:1)
+	fn on_initialize(r: u32, ) -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add((150_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((53 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(T::DbWeight::get().writes((67 as Weight).saturating_mul(r as Weight)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: Oracle Feeds (r:1 w:1)
+	// Storage: Oracle FeedCount (r:1 w:1)
+	fn register_feed() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Oracle Feeds (r:1 w:1)
+	// Storage: Oracle Bonds (r:16 w:16)
+	// Storage: System Account (r:16 w:16)
+	// Storage: Oracle Strikes (r:0 w:16)
+	// Storage: Oracle Suspended (r:0 w:16)
+	// Storage: Oracle FeedCount (r:1 w:1)
+	// Storage: Oracle Rounds (r:1 w:1)
+	// Storage: Oracle Closing (r:1 w:1)
+	// Storage: Oracle Values (r:0 w:1)
+	// Storage: Oracle History (r:0 w:1)
+	// Storage: Oracle MaxChange (r:0 w:1)
+	// Storage: Oracle Paused (r:0 w:1)
+	// Storage: Oracle Endpoints (r:0 w:1)
+	fn remove_feed() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(36 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(73 as Weight))
+	}
+	// Storage: Oracle Feeds (r:1 w:1)
+	fn add_feeder() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Oracle Feeds (r:1 w:1)
+	// Storage: Oracle Bonds (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Oracle Strikes (r:0 w:1)
+	// Storage: Oracle Suspended (r:0 w:1)
+	fn remove_feeder() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Oracle Feeds (r:1 w:0)
+	// Storage: Oracle Paused (r:1 w:0)
+	// Storage: Oracle Suspended (r:1 w:0)
+	// Storage: Oracle Slashing (r:1 w:0)
+	// Storage: Oracle Bonds (r:1 w:0)
+	// Storage: Oracle Rounds (r:1 w:1)
+	// Storage: Oracle Closing (r:1 w:1)
+	fn submit() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Oracle Slashing (r:0 w:1)
+	fn set_slashing_config() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Oracle Slashing (r:1 w:0)
+	// Storage: Oracle Feeds (r:1 w:0)
+	// Storage: Oracle Bonds (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn bond() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Oracle Bonds (r:1 w:1)
+	// Storage: Oracle Rounds (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	fn unbond() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Oracle Suspended (r:1 w:1)
+	// Storage: Oracle Strikes (r:0 w:1)
+	fn reinstate() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Oracle Feeds (r:1 w:0)
+	// Storage: Oracle MaxChange (r:0 w:1)
+	fn set_max_change() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Oracle Feeds (r:1 w:0)
+	// Storage: Oracle Paused (r:1 w:1)
+	// Storage: Oracle Rounds (r:1 w:1)
+	// Storage: Oracle Closing (r:1 w:1)
+	fn pause_feed() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Oracle Paused (r:1 w:1)
+	fn resume_feed() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Oracle Feeds (r:1 w:0)
+	// Storage: Oracle Endpoints (r:1 w:1)
+	fn add_endpoint() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Oracle Endpoints (r:1 w:1)
+	fn remove_endpoint() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Oracle Closing (r:1 w:1)
+	// Storage: Oracle Rounds (r:1 w:1)
+	// Storage: Oracle Feeds (r:1 w:0)
+	// Storage: Oracle Slashing (r:1 w:0)
+	// Storage: Oracle MaxChange (r:1 w:0)
+	// Storage: Oracle Values (r:1 w:1)
+	// Storage: Oracle Strikes (r:16 w:16)
+	// Storage: Oracle Bonds (r:16 w:16)
+	// Storage: System Account (r:16 w:16)
+	// Storage: Oracle Suspended (r:0 w:16)
+	// Storage: Oracle History (r:1 w:1)
+	fn on_initialize(r: u32, ) -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add((150_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((53 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((67 as Weight).saturating_mul(r as Weight)))
+	}
+}